@@ -1,5 +1,9 @@
 use crate::errors::Error;
-use crate::winning_combinations::get_is_winning_combination;
+use crate::position::{position_of_vector, vector_of_position};
+use crate::winning_combinations::{
+    canonical_winning_lines, get_is_winning_combination, lines_through, lines_through_counts,
+};
+use std::fmt;
 
 // Every board cell is associated with an uppercase latin letter
 // or the asterisc for the center. To enumerate cells, start from the center,
@@ -96,7 +100,7 @@ use crate::winning_combinations::get_is_winning_combination;
 // coordinate in base 3, that is:
 //
 // ```
-// x, y, z -> x * 9 + y * 3 + z
+// x, y, z -> x + y * 3 + z * 9
 // ```
 pub static POSITION: [char; 27] = [
     'A', 'H', 'G', 'B', 'I', 'F', 'C', 'D', 'E', // First layer, `z = 0`.
@@ -104,16 +108,175 @@ pub static POSITION: [char; 27] = [
     'R', 'X', 'Y', 'S', 'Z', 'W', 'T', 'U', 'V', // Third layer, `z = 2`.
 ];
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     IsPlaying,
     HasWinner,
     Tie,
 }
 
+// Positions one step away from the given one along a single axis.
+fn neighbors(position: char) -> Vec<char> {
+    let Some((x, y, z)) = vector_of_position(position) else {
+        return Vec::new();
+    };
+    POSITION
+        .into_iter()
+        .filter(|&p| {
+            let (a, b, c) = vector_of_position(p).unwrap();
+            x.abs_diff(a) + y.abs_diff(b) + z.abs_diff(c) == 1
+        })
+        .collect()
+}
+
+// Category of a position given by how many of its coordinates are 1.
+enum PositionCategory {
+    Corner,
+    Edge,
+    FaceCenter,
+    Center,
+}
+
+fn position_category(position: char) -> Option<PositionCategory> {
+    let (x, y, z) = vector_of_position(position)?;
+    match [x, y, z]
+        .into_iter()
+        .filter(|&coordinate| coordinate == 1)
+        .count()
+    {
+        0 => Some(PositionCategory::Corner),
+        1 => Some(PositionCategory::Edge),
+        2 => Some(PositionCategory::FaceCenter),
+        _ => Some(PositionCategory::Center),
+    }
+}
+
+// Permutations of the coordinates, paired with whether they are odd.
+const PERMUTATIONS: [([usize; 3], bool); 6] = [
+    ([0, 1, 2], false),
+    ([1, 2, 0], false),
+    ([2, 0, 1], false),
+    ([0, 2, 1], true),
+    ([2, 1, 0], true),
+    ([1, 0, 2], true),
+];
+
+// The 48 symmetries of the cube, starting from the 24 rotations, the first
+// one being the identity, followed by the reflections. Every symmetry maps
+// positions indexed like the `POSITION` array.
+//
+// A symmetry permutes coordinates and flips some of them, that is `c -> 2 - c`.
+// Rotations keep the orientation: an odd permutation needs an odd number of
+// flips.
+fn symmetries() -> Vec<[char; 27]> {
+    let mut symmetries = Vec::with_capacity(48);
+    for is_rotation in [true, false] {
+        for (permutation, is_odd) in PERMUTATIONS {
+            for flips in 0..8_u8 {
+                if ((flips.count_ones() % 2 == 1) == is_odd) != is_rotation {
+                    continue;
+                }
+                symmetries.push(POSITION.map(|position| {
+                    let (x, y, z) = vector_of_position(position).unwrap();
+                    let coordinates = [x, y, z];
+                    let [x, y, z] = [0, 1, 2].map(|axis| {
+                        let coordinate = coordinates[permutation[axis]];
+                        if flips & (1 << axis) == 0 {
+                            coordinate
+                        } else {
+                            2 - coordinate
+                        }
+                    });
+                    position_of_vector((x, y, z)).unwrap()
+                }));
+            }
+        }
+    }
+    symmetries
+}
+
+// The 24 rotations of the cube, starting from the identity.
+fn rotations() -> Vec<[char; 27]> {
+    let mut rotations = symmetries();
+    rotations.truncate(24);
+    rotations
+}
+
+fn position_index(position: char) -> usize {
+    POSITION.iter().position(|&p| p == position).unwrap()
+}
+
+/// Three positions forming a winning combination.
+pub type WinningCombination = (char, char, char);
+
+/// Count how many of the given masks are set at every position.
+/// Masks are indexed like the `POSITION` array, see [`Board::ownership_mask`].
+pub fn combine_masks(masks: &[[bool; 27]]) -> [u8; 27] {
+    let mut counts = [0; 27];
+    for mask in masks {
+        for (count, &is_set) in counts.iter_mut().zip(mask) {
+            *count += u8::from(is_set);
+        }
+    }
+    counts
+}
+
+/// Notation of the given game, as a string of positions in the order they
+/// were played, mapped by the rotation of the cube giving the smallest
+/// string. Games that are rotations of each other share the same canonical
+/// notation.
+///
+/// ```
+/// # use tris3d::board::canonical_notation;
+/// assert_eq!(canonical_notation("A").unwrap(), canonical_notation("C").unwrap());
+/// ```
+pub fn canonical_notation(notation: &str) -> Result<String, Error> {
+    let mut board = Board::new();
+    for position in notation.chars() {
+        board.add_move(position)?;
+    }
+    Ok(board
+        .augment()
+        .into_iter()
+        .map(String::from_iter)
+        .min()
+        .unwrap())
+}
+
+/// Number of positions of every category taken by a player.
+#[derive(Debug, Default, PartialEq)]
+pub struct StructureStats {
+    pub corners: u8,
+    pub edges: u8,
+    pub face_centers: u8,
+    pub center: u8,
+}
+
+/// State of a winning combination on the board.
+#[derive(Debug, PartialEq)]
+pub enum LineState {
+    /// No position is taken.
+    Empty,
+    /// Some positions are taken, all of them by the same player.
+    Owned { player_index: u8, num_positions: u8 },
+    /// Positions are taken by different players, nobody can complete it.
+    Dead,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Board {
     pub status: Status,
     moves: Vec<char>,
+    // Players move in turn, so the player of a move is its index modulo this.
+    num_players: usize,
+    // In the gravity variant, a position above the first layer can only be
+    // taken once the one below it is taken.
+    gravity: bool,
+    // Look for winning combinations among every position of the player who
+    // moved last, not only those through the last move.
+    exhaustive_win_scan: bool,
 }
 
 impl Board {
@@ -121,15 +284,228 @@ impl Board {
     pub fn new() -> Self {
         Self {
             moves: Vec::new(),
+            num_players: 3,
+            gravity: false,
+            exhaustive_win_scan: false,
             status: Status::IsPlaying,
         }
     }
 
-    /// Add a move to the board.
-    /// Return the number of winning combinations.
-    pub fn add_move(&mut self, position: char) -> Result<u8, Error> {
+    /// Create an empty board for the gravity variant, where positions stack
+    /// along the `z` axis: a position can only be taken once the one below
+    /// it is taken. See also [`Board::drop`].
+    pub fn with_gravity() -> Self {
+        Self {
+            gravity: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create an empty board for the given number of players, either 2 or 3.
+    pub fn with_num_players(num_players: usize) -> Result<Self, Error> {
+        if !(2..=3).contains(&num_players) {
+            return Err(Error::InvalidNumberOfPlayers);
+        }
+        Ok(Self {
+            num_players,
+            ..Self::new()
+        })
+    }
+
+    /// Check every winning combination of the player who moved last, from the
+    /// first move on, instead of only those through the last move once a win
+    /// is possible. Both scans agree on boards built by adding moves, this one
+    /// audits moves lists built in other ways.
+    pub fn with_exhaustive_win_scan(mut self, exhaustive: bool) -> Self {
+        self.exhaustive_win_scan = exhaustive;
+        self
+    }
+
+    // Take back every move, keeping the number of players and the rules.
+    pub(crate) fn clear(&mut self) {
+        self.moves.clear();
+        self.status = Status::IsPlaying;
+    }
+
+    /// Number of players taking turns on the board.
+    pub fn num_players(&self) -> usize {
+        self.num_players
+    }
+
+    /// Weight of every position for evaluation functions, that is the number
+    /// of winning combinations through it, see [`lines_through_counts`].
+    /// Weights are indexed like the `POSITION` array.
+    pub fn cell_weights() -> [u8; 27] {
+        lines_through_counts()
+    }
+
+    /// Create a board from the index of the player who took every position,
+    /// indexed like the `POSITION` array. This is the inverse of
+    /// [`Board::ownership`].
+    ///
+    /// Move order is not part of the ownership: positions of every player are
+    /// played in the order of the `POSITION` array, except for the last move
+    /// which completes the winning combinations or ends the game in a tie.
+    /// Return `Error::InconsistentState` if the moves cannot be replayed, for
+    /// instance if two winning combinations have no position in common.
+    ///
+    /// ```
+    /// # use tris3d::board::Board;
+    /// let mut ownership = [None; 27];
+    /// ownership[13] = Some(0);
+    /// let board = Board::from_ownership_array(ownership).unwrap();
+    /// assert_eq!(board.get_moves(), ['*']);
+    /// assert_eq!(board.ownership(), ownership);
+    /// ```
+    pub fn from_ownership_array(ownership: [Option<u8>; 27]) -> Result<Self, Error> {
+        Self::from_ownership(ownership, 3)
+    }
+
+    // Create a board for the given number of players from the ownership of
+    // every position, see `from_ownership_array`.
+    pub(crate) fn from_ownership(
+        ownership: [Option<u8>; 27],
+        num_players: usize,
+    ) -> Result<Self, Error> {
+        let mut board = Self::with_num_players(num_players)?;
+        let mut positions_by_player = vec![Vec::new(); num_players];
+        for (position, owner) in POSITION.into_iter().zip(ownership) {
+            if let Some(player_index) = owner {
+                let Some(positions) = positions_by_player.get_mut(usize::from(player_index)) else {
+                    return Err(Error::InconsistentState);
+                };
+                positions.push(position);
+            }
+        }
+        // Players move in turn, starting from the first one.
+        let num_moves_by_player: Vec<usize> = positions_by_player.iter().map(Vec::len).collect();
+        if num_moves_by_player.windows(2).any(|pair| pair[0] < pair[1])
+            || num_moves_by_player[0] > num_moves_by_player[num_players - 1] + 1
+        {
+            return Err(Error::InconsistentState);
+        }
+        let num_moves: usize = num_moves_by_player.iter().sum();
+        let completed_lines: Vec<[char; 3]> = canonical_winning_lines()
+            .iter()
+            .map(|&(a, b, c)| [a, b, c])
+            .filter(|line| {
+                let owners = line.map(|position| ownership[position_index(position)]);
+                owners[0].is_some() && owners.iter().all(|&owner| owner == owners[0])
+            })
+            .collect();
+        if !completed_lines.is_empty() {
+            // Only the player who moved last can have a winning combination,
+            // and their last move must complete it, as when playing. Pick the
+            // position shared by most combinations, in case of a double win.
+            let player_index = (num_moves - 1) % num_players;
+            if completed_lines
+                .iter()
+                .any(|line| ownership[position_index(line[0])] != Some(player_index as u8))
+            {
+                return Err(Error::InconsistentState);
+            }
+            let positions = &mut positions_by_player[player_index];
+            let last_move = *positions
+                .iter()
+                .max_by_key(|&position| {
+                    completed_lines
+                        .iter()
+                        .filter(|line| line.contains(position))
+                        .count()
+                })
+                .unwrap();
+            positions.retain(|&position| position != last_move);
+            positions.push(last_move);
+        } else if num_moves > 0 {
+            // No move is accepted after a tie, so if every winning combination
+            // is dead the last move must be one that kept some alive without it.
+            // Taking stones away never kills a combination, so earlier moves
+            // cannot end in a tie either.
+            let positions = &mut positions_by_player[(num_moves - 1) % num_players];
+            let keeps_line_alive = |position: char| {
+                lines_through(position).iter().any(|&(a, b, c)| {
+                    let owners: Vec<u8> = [a, b, c]
+                        .into_iter()
+                        .filter(|&p| p != position)
+                        .filter_map(|p| ownership[position_index(p)])
+                        .collect();
+                    owners.windows(2).all(|pair| pair[0] == pair[1])
+                })
+            };
+            if let Some(last_move) = positions.iter().copied().find(|&p| keeps_line_alive(p)) {
+                positions.retain(|&position| position != last_move);
+                positions.push(last_move);
+            }
+        }
+        // Replaying the moves rejects winning combinations completed before
+        // the last move, and moves following a tie.
+        for i in 0..num_moves {
+            board
+                .add_move(positions_by_player[i % num_players][i / num_players])
+                .map_err(|_| Error::InconsistentState)?;
+        }
+        Ok(board)
+    }
+
+    /// Encode the ownership of positions as runs of positions with the same
+    /// owner, in the order of the `POSITION` array. Every run takes one byte:
+    /// the owner in the two highest bits, that is 0 if empty or the player
+    /// index plus one, and the run length in the other bits.
+    ///
+    /// Boards of a two player game start with a byte with the number of
+    /// players in the two highest bits and a run length of zero.
+    ///
+    /// Move order is not encoded, see [`Board::from_ownership_array`].
+    ///
+    /// ```
+    /// # use tris3d::board::Board;
+    /// assert_eq!(Board::new().compress(), [27]);
+    /// ```
+    pub fn compress(&self) -> Vec<u8> {
+        let mut data: Vec<u8> = Vec::new();
+        if self.num_players != 3 {
+            data.push((self.num_players as u8) << 6);
+        }
+        for owner in self.ownership() {
+            let owner = owner.map_or(0, |player_index| player_index + 1) << 6;
+            match data.last_mut() {
+                Some(run) if *run & 0b1100_0000 == owner => *run += 1,
+                _ => data.push(owner | 1),
+            }
+        }
+        data
+    }
+
+    /// Create a board from data encoded by [`Board::compress`].
+    pub fn decompress(data: &[u8]) -> Result<Self, Error> {
+        let (num_players, data) = match data {
+            [header, runs @ ..] if header & 0b0011_1111 == 0 => (usize::from(header >> 6), runs),
+            _ => (3, data),
+        };
+        let mut ownership = Vec::with_capacity(27);
+        for &run in data {
+            let owner = match run >> 6 {
+                0 => None,
+                owner => Some(owner - 1),
+            };
+            let length = usize::from(run & 0b0011_1111);
+            if length == 0 || ownership.len() + length > 27 {
+                return Err(Error::InconsistentState);
+            }
+            ownership.extend(std::iter::repeat_n(owner, length));
+        }
+        let ownership = ownership.try_into().map_err(|_| Error::InconsistentState)?;
+        Self::from_ownership(ownership, num_players)
+    }
+
+    /// Check that a move can be added to the board, without adding it.
+    pub fn validate_move(&self, position: char) -> Result<(), Error> {
         if self.status == Status::Tie {
-            return Err(Error::BoardIsFull);
+            return Err(if self.moves.len() == 27 {
+                Error::BoardIsFull
+            } else {
+                Error::GameIsTied
+            });
         }
         if self.status == Status::HasWinner {
             return Err(Error::ThereIsAlreadyAWinner);
@@ -137,20 +513,28 @@ impl Board {
         if self.moves.contains(&position) {
             return Err(Error::PositionAlreadyTaken);
         }
-        let mut position_is_valid = false;
-        for p in POSITION {
-            if position == p {
-                position_is_valid = true;
-                self.moves.push(position);
-                break;
-            }
-        }
-        if !position_is_valid {
+        if !POSITION.contains(&position) {
             return Err(Error::InvalidPosition);
         }
+        if self.gravity {
+            let index = position_index(position);
+            if index >= 9 && !self.moves.contains(&POSITION[index - 9]) {
+                return Err(Error::PositionBelowIsEmpty);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a move to the board.
+    /// Return the number of winning combinations.
+    /// On error, the board is left unchanged.
+    pub fn add_move(&mut self, position: char) -> Result<u8, Error> {
+        self.validate_move(position)?;
+        // Every check passed, the board can be modified.
+        self.moves.push(position);
         let num_winning_combinations = self.get_num_winning_combinations();
         if num_winning_combinations == 0 {
-            if self.moves.len() == 27 {
+            if self.is_draw_inevitable() {
                 self.status = Status::Tie;
             }
             Ok(num_winning_combinations)
@@ -160,34 +544,760 @@ impl Board {
         }
     }
 
+    /// Add a move, run the given function on the resulting board and
+    /// then take the move back.
+    /// Return the result of the function.
+    pub fn with_move<T>(
+        &mut self,
+        position: char,
+        f: impl FnOnce(&Board) -> T,
+    ) -> Result<T, Error> {
+        self.add_move(position)?;
+        let result = f(self);
+        self.undo_move()?;
+        Ok(result)
+    }
+
+    /// Positions where a move can be added, in the order of the `POSITION`
+    /// array. Return nothing once the game is over.
+    pub fn available_positions(&self) -> Vec<char> {
+        if self.status != Status::IsPlaying {
+            return Vec::new();
+        }
+        POSITION
+            .into_iter()
+            .filter(|&position| self.validate_move(position).is_ok())
+            .collect()
+    }
+
+    /// Check if a move can be added at the given position, that is if it is
+    /// one of the `available_positions`.
+    pub fn is_available(&self, position: char) -> bool {
+        self.validate_move(position).is_ok()
+    }
+
+    /// Take back the last move and return its position.
+    pub fn undo_move(&mut self) -> Result<char, Error> {
+        let position = self.moves.pop().ok_or(Error::NoMoveToUndo)?;
+        // No move is accepted once the game is over, so it was playing before.
+        self.status = Status::IsPlaying;
+        Ok(position)
+    }
+
+    /// Add a move in the gravity variant, where the position falls to the
+    /// lowest empty layer of the column with the given `x` and `y` coordinates.
+    /// Return the position taken.
+    pub fn drop(&mut self, x: u8, y: u8) -> Result<char, Error> {
+        if x >= 3 || y >= 3 {
+            return Err(Error::InvalidPosition);
+        }
+        let Some(position) = (0..3)
+            .map(|z| POSITION[usize::from(x + y * 3 + z * 9)])
+            .find(|&position| !self.moves.contains(&position))
+        else {
+            return Err(Error::ColumnFull);
+        };
+        self.add_move(position)?;
+        Ok(position)
+    }
+
     pub fn get_num_moves(&self) -> usize {
         self.moves.len()
     }
 
-    /// Check if there is any winner.
-    pub fn get_num_winning_combinations(&self) -> u8 {
-        let mut num_winning_combinations = 0;
-        let num_moves = self.moves.len();
-        // No player can win before the seventh move.
-        if num_moves < 7 {
+    /// Positions played so far, in move order.
+    pub fn get_moves(&self) -> &[char] {
+        &self.moves
+    }
+
+    /// Check if every player is still to make a first move.
+    pub fn is_opening(&self) -> bool {
+        self.moves.len() < self.num_players
+    }
+
+    /// Check if every player has at most one move left.
+    pub fn is_endgame(&self) -> bool {
+        self.moves.len() >= 27 - self.num_players
+    }
+
+    /// Render every position with its label and the index of the player who
+    /// took it, starting from 0 like everywhere else in the crate, or a dot if
+    /// it is empty. Layers are stacked as in the board labelling above, with
+    /// the `z = 2` layer on top. See also the `Display` implementation.
+    ///
+    /// ```text
+    /// T:. U:. V:.
+    /// S:. Z:. W:.
+    /// R:. X:. Y:.
+    ///
+    /// L:. M:. N:.
+    /// K:. *:1 O:.
+    /// J:. Q:. P:.
+    ///
+    /// C:. D:. E:.
+    /// B:. I:. F:.
+    /// A:0 H:. G:.
+    /// ```
+    pub fn render_labeled(&self) -> String {
+        let mut text = String::new();
+        for (z, y, row) in self.drawn_rows() {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|&(position, owner)| match owner {
+                    Some(owner) => format!("{position}:{owner}"),
+                    None => format!("{position}:."),
+                })
+                .collect();
+            text.push_str(&cells.join(" "));
+            if y > 0 {
+                text.push('\n');
+            } else if z > 0 {
+                text.push_str("\n\n");
+            }
+        }
+        text
+    }
+
+    // Rows of positions, with the index of the player who took them, in the
+    // order they are drawn: layers from `z = 2` at the top, rows from `y = 2`
+    // and positions from `x = 0`. Every row comes with its `z` and `y`.
+    fn drawn_rows(&self) -> impl Iterator<Item = (usize, usize, [(char, Option<usize>); 3])> + '_ {
+        (0..3).rev().flat_map(move |z| {
+            (0..3).rev().map(move |y| {
+                let row = [0, 1, 2].map(|x| {
+                    let position = POSITION[x + y * 3 + z * 9];
+                    (position, self.owner(position))
+                });
+                (z, y, row)
+            })
+        })
+    }
+
+    /// Count corners, edges, face centers and center taken by the given player.
+    pub fn structure_stats(&self, player_index: u8) -> StructureStats {
+        let mut stats = StructureStats::default();
+        for &position in self
+            .moves
+            .iter()
+            .skip(usize::from(player_index))
+            .step_by(self.num_players)
+        {
+            match position_category(position) {
+                Some(PositionCategory::Corner) => stats.corners += 1,
+                Some(PositionCategory::Edge) => stats.edges += 1,
+                Some(PositionCategory::FaceCenter) => stats.face_centers += 1,
+                Some(PositionCategory::Center) => stats.center += 1,
+                None => {}
+            }
+        }
+        stats
+    }
+
+    /// Last `n` positions played, in move order.
+    /// Return all of them if fewer than `n` moves were played.
+    pub fn recent_moves(&self, n: usize) -> &[char] {
+        &self.moves[self.moves.len().saturating_sub(n)..]
+    }
+
+    /// Index of the move that completed the first winning combination, if any.
+    pub fn winning_move_index(&self) -> Option<usize> {
+        if self.status == Status::HasWinner {
+            // No move is accepted once there is a winner, so it is the last one.
+            Some(self.moves.len() - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the given winning combination can still be completed by someone,
+    /// that is it has no positions taken by two different players.
+    pub fn is_line_live(&self, line: (char, char, char)) -> Result<bool, Error> {
+        if !get_is_winning_combination(line.0, line.1, line.2)? {
+            return Err(Error::NotAWinningCombination);
+        }
+        Ok(self.line_state(line) != LineState::Dead)
+    }
+
+    /// Number of positions the given player still needs to complete the given
+    /// winning combination, or `None` if an opponent took any of them.
+    pub fn moves_to_complete(
+        &self,
+        player_index: u8,
+        line: (char, char, char),
+    ) -> Result<Option<u8>, Error> {
+        if !get_is_winning_combination(line.0, line.1, line.2)? {
+            return Err(Error::NotAWinningCombination);
+        }
+        Ok(match self.line_state(line) {
+            LineState::Empty => Some(3),
+            LineState::Owned {
+                player_index: owner,
+                num_positions,
+            } if owner == player_index => Some(3 - num_positions),
+            _ => None,
+        })
+    }
+
+    /// State of every winning combination passing through the center.
+    pub fn center_lines_status(&self) -> Vec<((char, char, char), LineState)> {
+        canonical_winning_lines()
+            .iter()
+            .copied()
+            .filter(|line| [line.0, line.1, line.2].contains(&'*'))
+            .map(|line| (line, self.line_state(line)))
+            .collect()
+    }
+
+    fn line_state(&self, line: (char, char, char)) -> LineState {
+        let mut state = LineState::Empty;
+        for position in [line.0, line.1, line.2] {
+            let Some(owner) = self.owner(position) else {
+                continue;
+            };
+            state = match state {
+                LineState::Empty => LineState::Owned {
+                    player_index: owner as u8,
+                    num_positions: 1,
+                },
+                LineState::Owned {
+                    player_index,
+                    num_positions,
+                } if usize::from(player_index) == owner => LineState::Owned {
+                    player_index,
+                    num_positions: num_positions + 1,
+                },
+                _ => return LineState::Dead,
+            };
+        }
+        state
+    }
+
+    /// Neighbors of the given position taken by the given player.
+    pub fn adjacent_friendly(&self, position: char, player_index: u8) -> Vec<char> {
+        neighbors(position)
+            .into_iter()
+            .filter(|&p| self.owner(p) == Some(usize::from(player_index)))
+            .collect()
+    }
+
+    /// Index of the players who took the positions of the given `z` layer.
+    /// The result is indexed by `x` first, then `y`.
+    pub fn layer(&self, z: u8) -> Result<[[Option<u8>; 3]; 3], Error> {
+        if z >= 3 {
+            return Err(Error::InvalidLayer);
+        }
+        let mut layer = [[None; 3]; 3];
+        for (x, row) in layer.iter_mut().enumerate() {
+            for (y, cell) in row.iter_mut().enumerate() {
+                let position = POSITION[x + y * 3 + usize::from(z) * 9];
+                *cell = self.owner(position).map(|owner| owner as u8);
+            }
+        }
+        Ok(layer)
+    }
+
+    /// Static evaluation of the board from the point of view of the given player.
+    ///
+    /// Every winning combination with positions taken by a single player is
+    /// worth a point to that player, and another point if it is an open threat,
+    /// that is only one position is missing. The result is the player's points
+    /// minus the points of the opponents.
+    ///
+    /// Once somebody completed a winning combination, the result is `i32::MAX`
+    /// if the player won, `-i32::MAX` otherwise, so that a win always beats
+    /// any threat.
+    pub fn evaluate_for(&self, player_index: u8) -> i32 {
+        if self.status == Status::HasWinner {
+            return if (self.moves.len() - 1) % self.num_players == usize::from(player_index) {
+                i32::MAX
+            } else {
+                -i32::MAX
+            };
+        }
+        let mut score = 0;
+        for &(position_a, position_b, position_c) in canonical_winning_lines() {
+            let owners: Vec<usize> = [position_a, position_b, position_c]
+                .into_iter()
+                .filter_map(|p| self.owner(p))
+                .collect();
+            let Some(&line_owner) = owners.first() else {
+                continue;
+            };
+            if owners.iter().any(|&owner| owner != line_owner) {
+                continue;
+            }
+            let points = if owners.len() == 2 { 2 } else { 1 };
+            if line_owner == usize::from(player_index) {
+                score += points;
+            } else {
+                score -= points;
+            }
+        }
+        score
+    }
+
+    /// Check if the given player can still complete a winning combination,
+    /// that is a combination with no positions taken by opponents and no more
+    /// empty positions than the moves left to the player.
+    /// Return `false` once the game is over.
+    pub fn can_still_win(&self, player_index: u8) -> bool {
+        if self.status != Status::IsPlaying {
+            return false;
+        }
+        let num_moves_left = (self.moves.len()..27)
+            .filter(|i| i % self.num_players == usize::from(player_index))
+            .count();
+        canonical_winning_lines().iter().any(|&line| {
+            let num_empty_positions = match self.line_state(line) {
+                LineState::Empty => 3,
+                LineState::Owned {
+                    player_index: owner,
+                    num_positions,
+                } if owner == player_index => 3 - num_positions,
+                _ => return false,
+            };
+            usize::from(num_empty_positions) <= num_moves_left
+        })
+    }
+
+    /// Check if every winning combination has positions taken by two different
+    /// players, so that nobody can win anymore. This is always the case once
+    /// the board is full, unless somebody won.
+    pub fn is_draw_inevitable(&self) -> bool {
+        self.status != Status::HasWinner
+            && canonical_winning_lines()
+                .iter()
+                .all(|&line| self.line_state(line) == LineState::Dead)
+    }
+
+    /// Hash of the ownership of every position and of the status, computed with
+    /// 32 bits FNV-1a.
+    ///
+    /// Move order is not included: boards where every position is taken by the
+    /// same player have the same checksum.
+    pub fn checksum(&self) -> u32 {
+        let status = match self.status {
+            Status::IsPlaying => 0,
+            Status::HasWinner => 1,
+            Status::Tie => 2,
+        };
+        let mut hash: u32 = 0x811c_9dc5;
+        for byte in self
+            .ownership()
+            .into_iter()
+            .map(|owner| owner.map_or(0, |player_index| player_index + 1))
+            .chain([status])
+        {
+            hash ^= u32::from(byte);
+            hash = hash.wrapping_mul(0x0100_0193);
+        }
+        hash
+    }
+
+    /// Empty positions that would complete a winning combination for the given
+    /// player, in the order of the `POSITION` array.
+    pub fn threats(&self, player_index: u8) -> Vec<char> {
+        POSITION
+            .into_iter()
+            .filter(|&position| self.move_wins(position, player_index) == Ok(true))
+            .collect()
+    }
+
+    /// Check if taking the given empty position would complete a winning
+    /// combination for the given player. Only combinations passing through
+    /// the position are considered.
+    pub fn move_wins(&self, position: char, player_index: u8) -> Result<bool, Error> {
+        if vector_of_position(position).is_none() {
+            return Err(Error::InvalidPosition);
+        }
+        if self.owner(position).is_some() {
+            return Err(Error::PositionAlreadyTaken);
+        }
+        let player_index = Some(usize::from(player_index));
+        Ok(lines_through(position)
+            .iter()
+            .any(|&(position_a, position_b, position_c)| {
+                [position_a, position_b, position_c]
+                    .into_iter()
+                    .all(|p| p == position || self.owner(p) == player_index)
+            }))
+    }
+
+    /// Suggest a move for the player in turn, picking the first of:
+    ///
+    /// 1. a position completing a winning combination for the player;
+    /// 2. a position completing a winning combination for an opponent,
+    ///    starting from the next player in turn;
+    /// 3. the center, if empty;
+    /// 4. any empty position.
+    ///
+    /// Only `available_positions` are considered, so that positions floating
+    /// above empty ones are never suggested on a board with gravity.
+    /// Among equally good positions, the first in the order of the `POSITION`
+    /// array is picked. Return `None` only if the game is over.
+    pub fn suggest_move(&self) -> Option<char> {
+        let available_positions = self.available_positions();
+        let num_players = self.num_players as u8;
+        let player_index = (self.moves.len() % self.num_players) as u8;
+        (0..num_players)
+            .find_map(|i| {
+                self.threats((player_index + i) % num_players)
+                    .into_iter()
+                    .find(|position| available_positions.contains(position))
+            })
+            .or_else(|| available_positions.contains(&'*').then_some('*'))
+            .or_else(|| available_positions.first().copied())
+    }
+
+    /// Empty positions that, if taken by the given player, create two or more
+    /// threats, paired with the winning combinations the player could then
+    /// complete with one more move.
+    pub fn all_forks(&self, player_index: u8) -> Vec<(char, Vec<WinningCombination>)> {
+        let player_index = Some(usize::from(player_index));
+        self.available_positions()
+            .into_iter()
+            .filter_map(|position| {
+                let lines: Vec<WinningCombination> = lines_through(position)
+                    .iter()
+                    .copied()
+                    .filter(|&(position_a, position_b, position_c)| {
+                        let owners = [position_a, position_b, position_c].map(|p| self.owner(p));
+                        owners
+                            .iter()
+                            .filter(|&&owner| owner == player_index)
+                            .count()
+                            == 1
+                            && owners.iter().filter(|owner| owner.is_none()).count() == 2
+                    })
+                    .collect();
+                (lines.len() >= 2).then_some((position, lines))
+            })
+            .collect()
+    }
+
+    /// How close taking the given position brings the player in turn to
+    /// completing a winning combination: the maximum number of positions the
+    /// player already took on any combination through it with no opponent
+    /// positions. Return 0 if the position is taken or invalid.
+    pub fn threat_proximity(&self, position: char) -> u8 {
+        if self.owner(position).is_some() {
             return 0;
         }
-        // Get all combinations of current player and count how many are winning combinations.
-        let current_player_index = (num_moves - 1) % 3;
-        for i in (current_player_index..num_moves).step_by(3) {
-            for j in ((i + 3)..num_moves).step_by(3) {
-                for k in ((j + 3)..num_moves).step_by(3) {
-                    let is_winning_combination =
-                        get_is_winning_combination(self.moves[i], self.moves[j], self.moves[k])
-                            .unwrap();
-                    if is_winning_combination {
-                        num_winning_combinations += 1;
+        let player_index = self.moves.len() % self.num_players;
+        lines_through(position)
+            .iter()
+            .filter_map(|&(position_a, position_b, position_c)| {
+                let mut num_positions = 0;
+                for owner in [position_a, position_b, position_c].map(|p| self.owner(p)) {
+                    match owner {
+                        Some(owner) if owner == player_index => num_positions += 1,
+                        Some(_) => return None,
+                        None => {}
                     }
                 }
+                Some(num_positions)
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Empty positions that, if taken by the player in turn, leave no player
+    /// able to complete a winning combination, see [`Board::can_still_win`].
+    /// Positions that win the game are not included.
+    pub fn drawing_moves(&self) -> Vec<char> {
+        let mut board = self.clone();
+        POSITION
+            .into_iter()
+            .filter(|&position| {
+                board
+                    .with_move(position, |board| {
+                        board.status != Status::HasWinner
+                            && (0..board.num_players as u8)
+                                .all(|player_index| !board.can_still_win(player_index))
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Move for the given player, in turn, with the best outcome assuming
+    /// every opponent plays against that player, looking at most `max_depth`
+    /// moves ahead. Losing later is better than losing sooner.
+    /// Return `None` if the player is not in turn or the game is over.
+    pub fn best_defense(&self, player_index: u8, max_depth: u8) -> Option<char> {
+        if self.status != Status::IsPlaying
+            || self.moves.len() % self.num_players != usize::from(player_index)
+        {
+            return None;
+        }
+        let mut best: Option<(char, i32)> = None;
+        for position in POSITION {
+            let mut board = self.clone();
+            if board.add_move(position).is_err() {
+                continue;
+            }
+            let score = board.worst_case_score(player_index, max_depth.saturating_sub(1));
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((position, score));
+            }
+        }
+        best.map(|(position, _)| position)
+    }
+
+    // Score of the board for the given player, with the player maximizing it
+    // and opponents minimizing it. Outcomes are scored higher or lower the
+    // sooner they happen, that is the more depth is left.
+    pub(crate) fn worst_case_score(&self, player_index: u8, depth: u8) -> i32 {
+        match self.status {
+            Status::HasWinner => {
+                let score = 100 + i32::from(depth);
+                return if (self.moves.len() - 1) % self.num_players == usize::from(player_index) {
+                    score
+                } else {
+                    -score
+                };
+            }
+            Status::Tie => return 0,
+            Status::IsPlaying if depth == 0 => return 0,
+            Status::IsPlaying => {}
+        }
+        let scores = POSITION.into_iter().filter_map(|position| {
+            let mut board = self.clone();
+            board.add_move(position).ok()?;
+            Some(board.worst_case_score(player_index, depth - 1))
+        });
+        if self.moves.len() % self.num_players == usize::from(player_index) {
+            scores.max().unwrap_or(0)
+        } else {
+            scores.min().unwrap_or(0)
+        }
+    }
+
+    /// List every winning combination with a flag per player telling whether
+    /// that player can still complete it, that is no opponent took any of its
+    /// positions.
+    ///
+    /// The flags are a `Vec` rather than an array because their length depends
+    /// on the board: there is one flag per player, that is `num_players()` of
+    /// them, indexed by player index.
+    pub fn live_lines_matrix(&self) -> Vec<((char, char, char), Vec<bool>)> {
+        canonical_winning_lines()
+            .iter()
+            .copied()
+            .map(|line| {
+                let owners = [line.0, line.1, line.2].map(|p| self.owner(p));
+                let is_live_for = (0..self.num_players)
+                    .map(|player_index| {
+                        owners
+                            .iter()
+                            .all(|&owner| owner.is_none() || owner == Some(player_index))
+                    })
+                    .collect();
+                (line, is_live_for)
+            })
+            .collect()
+    }
+
+    /// Index of the player who took every position, indexed like the `POSITION` array.
+    pub fn ownership(&self) -> [Option<u8>; 27] {
+        POSITION.map(|position| self.owner(position).map(|owner| owner as u8))
+    }
+
+    /// Exchange the positions taken by the two given players, keeping the
+    /// others in place, see [`Board::from_ownership_array`] for how moves are
+    /// ordered and status is recomputed.
+    ///
+    /// Turn order does not change, so swapping players with a different number
+    /// of positions, or moving a winning combination away from the player who
+    /// moved last, gives an `Error::InconsistentState`.
+    pub fn with_players_swapped(&self, a: u8, b: u8) -> Result<Self, Error> {
+        if usize::from(a.max(b)) >= self.num_players {
+            return Err(Error::InconsistentState);
+        }
+        let ownership = self.ownership().map(|owner| match owner {
+            Some(player_index) if player_index == a => Some(b),
+            Some(player_index) if player_index == b => Some(a),
+            _ => owner,
+        });
+        Self::from_ownership(ownership, self.num_players)
+    }
+
+    /// Move list rotated by every one of the 24 rotations of the cube, starting
+    /// from the identity. Moves keep their order, so every list can be replayed
+    /// on an empty board.
+    pub fn augment(&self) -> Vec<Vec<char>> {
+        rotations()
+            .iter()
+            .map(|rotation| {
+                self.moves
+                    .iter()
+                    .map(|&position| rotation[position_index(position)])
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Flag the positions taken by the given player, indexed like the
+    /// `POSITION` array.
+    pub fn ownership_mask(&self, player_index: u8) -> [bool; 27] {
+        self.ownership().map(|owner| owner == Some(player_index))
+    }
+
+    /// Legal moves, keeping only the first one in the order of the `POSITION`
+    /// array among moves mapped to each other by a symmetry of the cube that
+    /// leaves the board unchanged. On an empty board these are a corner, an
+    /// edge, a face center and the center.
+    pub fn distinct_moves_up_to_symmetry(&self) -> Vec<char> {
+        if self.status != Status::IsPlaying {
+            return Vec::new();
+        }
+        let ownership = self.ownership();
+        let stabilizer: Vec<[char; 27]> = symmetries()
+            .into_iter()
+            .filter(|symmetry| {
+                (0..27).all(|index| ownership[position_index(symmetry[index])] == ownership[index])
+            })
+            .collect();
+        let mut moves: Vec<char> = Vec::new();
+        for position in self.available_positions() {
+            let index = position_index(position);
+            if !stabilizer
+                .iter()
+                .any(|symmetry| moves.contains(&symmetry[index]))
+            {
+                moves.push(position);
+            }
+        }
+        moves
+    }
+
+    /// Check if the other board has the same positions taken by the same
+    /// players, up to one of the 48 symmetries of the cube, that is rotations
+    /// and reflections. Move order is not compared.
+    pub fn is_symmetric_to(&self, other: &Board) -> bool {
+        if self.num_players != other.num_players || self.moves.len() != other.moves.len() {
+            return false;
+        }
+        let ownership = self.ownership();
+        let other_ownership = other.ownership();
+        symmetries().into_iter().any(|symmetry| {
+            (0..27)
+                .all(|index| other_ownership[position_index(symmetry[index])] == ownership[index])
+        })
+    }
+
+    /// Index of the player who took the given position.
+    /// Return `None` if the position is free or invalid.
+    pub fn player_index_at(&self, position: char) -> Option<usize> {
+        self.owner(position)
+    }
+
+    // Index of the player who took the given position, if any.
+    fn owner(&self, position: char) -> Option<usize> {
+        self.moves
+            .iter()
+            .position(|&p| p == position)
+            .map(|index| index % self.num_players)
+    }
+
+    /// Index of the player who completed a winning combination, looking at
+    /// every player rather than only at the one who moved last.
+    /// Return `None` if nobody did, or if more than one player did, which
+    /// cannot happen in a game.
+    pub fn won_by(&self) -> Option<u8> {
+        match self.players_with_winning_combinations()[..] {
+            [player_index] => Some(player_index),
+            _ => None,
+        }
+    }
+
+    // Indexes of the players who completed any winning combination.
+    fn players_with_winning_combinations(&self) -> Vec<u8> {
+        let mut player_indexes = Vec::new();
+        for &(position_a, position_b, position_c) in canonical_winning_lines() {
+            let owner = self.owner(position_a);
+            if let Some(player_index) = owner {
+                let player_index = player_index as u8;
+                if owner == self.owner(position_b)
+                    && owner == self.owner(position_c)
+                    && !player_indexes.contains(&player_index)
+                {
+                    player_indexes.push(player_index);
+                }
             }
         }
+        player_indexes
+    }
+
+    /// Position shared by the winning combinations completed by the last move,
+    /// if it completed more than one.
+    pub fn shared_cell_of_double_win(&self) -> Option<char> {
+        let last_move = *self.moves.last()?;
+        let player_index = self.owner(last_move);
+        let completed_lines: Vec<[char; 3]> = canonical_winning_lines()
+            .iter()
+            .copied()
+            .map(|(a, b, c)| [a, b, c])
+            .filter(|line| {
+                line.contains(&last_move) && line.iter().all(|&p| self.owner(p) == player_index)
+            })
+            .collect();
+        if completed_lines.len() < 2 {
+            return None;
+        }
+        completed_lines[0]
+            .into_iter()
+            .find(|p| completed_lines.iter().all(|line| line.contains(p)))
+    }
+
+    /// Check if the last move completed a winning combination.
+    pub fn has_tris(&self) -> bool {
+        self.get_num_winning_combinations() > 0
+    }
+
+    /// Check if there is any winner.
+    pub fn get_num_winning_combinations(&self) -> u8 {
+        self.winning_combinations().len() as u8
+    }
 
-        num_winning_combinations
+    /// Winning combinations of the player who moved last, with positions in
+    /// move order.
+    pub fn winning_combinations(&self) -> Vec<(char, char, char)> {
+        let num_moves = self.moves.len();
+        // No player can win before the third move of the first player.
+        if num_moves == 0 || (num_moves <= 2 * self.num_players && !self.exhaustive_win_scan) {
+            return Vec::new();
+        }
+        // Move index of every position taken by the player who moved last.
+        let current_player_index = (num_moves - 1) % self.num_players;
+        let mut move_indexes = [None; 27];
+        for i in (current_player_index..num_moves).step_by(self.num_players) {
+            move_indexes[position_index(self.moves[i])] = Some(i);
+        }
+        // Unless the scan is exhaustive, only combinations through the last
+        // move are checked: the game would have ended earlier otherwise.
+        let lines = if self.exhaustive_win_scan {
+            canonical_winning_lines()
+        } else {
+            lines_through(self.moves[num_moves - 1])
+        };
+        let mut winning_combinations: Vec<[usize; 3]> = lines
+            .iter()
+            .filter_map(|&(position_a, position_b, position_c)| {
+                let mut indexes = [position_a, position_b, position_c]
+                    .map(|position| move_indexes[position_index(position)]);
+                indexes.sort_unstable();
+                let [Some(i), Some(j), Some(k)] = indexes else {
+                    return None;
+                };
+                Some([i, j, k])
+            })
+            .collect();
+        winning_combinations.sort_unstable();
+        winning_combinations
+            .into_iter()
+            .map(|[i, j, k]| (self.moves[i], self.moves[j], self.moves[k]))
+            .collect()
     }
 }
 
@@ -197,7 +1307,83 @@ impl Default for Board {
     }
 }
 
+// Draw layers stacked like in the `POSITION` docs, from `z = 2` at the top.
+// Empty cells show their position, taken cells the index of the player who
+// took them, like `render_labeled`.
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = Vec::new();
+        for (_, y, row) in self.drawn_rows() {
+            if y == 2 {
+                lines.push(format!("{}{}", " ".repeat(11), "_".repeat(22)));
+            }
+            let indent = 3 * y + 1;
+            let cells: Vec<String> = row
+                .iter()
+                .map(|&(position, owner)| match owner {
+                    Some(owner) => format!("   {owner}   "),
+                    None => format!("   {position}   "),
+                })
+                .collect();
+            lines.push(format!(
+                "{}/       /       /       /",
+                " ".repeat(indent + 2)
+            ));
+            lines.push(format!("{}/{}/", " ".repeat(indent + 1), cells.join("/")));
+            lines.push(format!("{}/_______/_______/_______/", " ".repeat(indent)));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// Generate only legal boards, replaying a sequence of legal moves.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Board {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut board = Board::new();
+        let num_moves = u.int_in_range(0..=27)?;
+        while board.moves.len() < num_moves && board.status == Status::IsPlaying {
+            let position = *u.choose(&board.available_positions())?;
+            board.add_move(position).unwrap();
+        }
+        Ok(board)
+    }
+}
+
+// Fields of a serialized board. Moves are replayed on deserialization, so
+// that an inconsistent state is rejected.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BoardState {
+    status: Status,
+    moves: Vec<char>,
+    num_players: usize,
+    #[serde(default)]
+    gravity: bool,
+    #[serde(default)]
+    exhaustive_win_scan: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = BoardState::deserialize(deserializer)?;
+        let to_custom_error = |error: Error| serde::de::Error::custom(format!("{error:?}"));
+        let mut board = Board::with_num_players(state.num_players).map_err(to_custom_error)?;
+        board.gravity = state.gravity;
+        board.exhaustive_win_scan = state.exhaustive_win_scan;
+        for position in state.moves {
+            board.add_move(position).map_err(to_custom_error)?;
+        }
+        if board.status != state.status {
+            return Err(to_custom_error(Error::InconsistentState));
+        }
+        Ok(board)
+    }
+}
+
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants, clippy::needless_range_loop)]
 mod tests {
     use super::*;
 
@@ -250,6 +1436,9 @@ mod tests {
             Board {
                 moves: vec!['A', 'H', 'G', '*', 'I', 'F', 'V'],
                 status: Status::IsPlaying,
+                num_players: 3,
+                gravity: false,
+                exhaustive_win_scan: false,
             }
             .get_num_winning_combinations(),
             1
@@ -257,22 +1446,1025 @@ mod tests {
     }
 
     #[test]
-    fn playing_results() {
-        for (positions, status, num_winning_combinations) in [
-            // First move.
-            (vec!['A'], Status::IsPlaying, 0),
-            (
-                vec!['A', 'B', 'C', '*', 'D', 'E', 'G', 'H'],
-                Status::IsPlaying,
-                0,
-            ),
-            // Player one wins will 'A', '*', 'V'.
-            (
-                vec!['A', 'B', 'C', '*', 'D', 'E', 'V'],
-                Status::HasWinner,
-                1,
-            ),
-            // Two winning combinations.
+    fn neighbors_works() {
+        assert_eq!(neighbors('A'), vec!['H', 'B', 'J']);
+        assert_eq!(neighbors('*'), vec!['I', 'Q', 'K', 'O', 'M', 'Z']);
+        assert_eq!(neighbors(' '), vec![]);
+    }
+
+    #[test]
+    fn adjacent_friendly_works_both_ways() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H', 'E'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.adjacent_friendly('A', 0), vec!['H']);
+        assert_eq!(board.adjacent_friendly('H', 0), vec!['A']);
+        assert_eq!(board.adjacent_friendly('A', 1), vec!['B']);
+        assert_eq!(board.adjacent_friendly('G', 0), vec!['H']);
+    }
+
+    #[test]
+    fn layer_works() {
+        let mut board = Board::new();
+        for p in ['A', 'Q', 'V', 'E', '*'] {
+            board.add_move(p).unwrap();
+        }
+        let first_layer = board.layer(0).unwrap();
+        assert_eq!(first_layer[0][0], Some(0));
+        assert_eq!(first_layer[2][2], Some(0));
+        assert_eq!(first_layer[1][1], None);
+        let second_layer = board.layer(1).unwrap();
+        assert_eq!(second_layer[1][0], Some(1));
+        assert_eq!(second_layer[1][1], Some(1));
+        let third_layer = board.layer(2).unwrap();
+        assert_eq!(third_layer[2][2], Some(2));
+        assert_eq!(third_layer[0][0], None);
+        assert_eq!(board.layer(3).unwrap_err(), Error::InvalidLayer);
+    }
+
+    #[test]
+    fn evaluate_for_favours_player_with_more_threats() {
+        let mut board = Board::new();
+        // First player threatens 'Q', third player positions are all blocked.
+        for p in ['*', 'C', 'D', 'M', 'I'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.evaluate_for(0), 6);
+        assert_eq!(board.evaluate_for(1), -6);
+        assert_eq!(board.evaluate_for(2), -20);
+
+        let mut board = Board::new();
+        for p in ['A', 'H', 'G', '*', 'I', 'F'] {
+            board.add_move(p).unwrap();
+        }
+        let score_with_threat = board.evaluate_for(0);
+        board.add_move('V').unwrap();
+        assert!(board.evaluate_for(0) > score_with_threat);
+        assert_eq!(board.evaluate_for(0), i32::MAX);
+        assert_eq!(board.evaluate_for(1), -i32::MAX);
+    }
+
+    #[test]
+    fn with_move_restores_board() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D', 'E'] {
+            board.add_move(p).unwrap();
+        }
+        let board_before = board.clone();
+        let (status, num_winning_combinations) = board
+            .with_move('V', |board| {
+                (board.status.clone(), board.get_num_winning_combinations())
+            })
+            .unwrap();
+        assert_eq!(status, Status::HasWinner);
+        assert_eq!(num_winning_combinations, 1);
+        assert_eq!(board, board_before);
+        assert_eq!(
+            board.with_move('A', |_| ()).unwrap_err(),
+            Error::PositionAlreadyTaken
+        );
+        assert_eq!(board, board_before);
+    }
+
+    #[test]
+    fn from_ownership_array_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D', 'E', 'V'] {
+            board.add_move(p).unwrap();
+        }
+        let rebuilt_board = Board::from_ownership_array(board.ownership()).unwrap();
+        assert_eq!(rebuilt_board.ownership(), board.ownership());
+        assert_eq!(rebuilt_board.status, Status::HasWinner);
+        assert_eq!(rebuilt_board.get_num_winning_combinations(), 1);
+    }
+
+    #[test]
+    fn from_ownership_array_ends_with_winning_move() {
+        let mut ownership = [None; 27];
+        for (positions, player_index) in [("AHGV", 0), ("BDK", 1), ("CFJ", 2)] {
+            for position in positions.chars() {
+                ownership[position_index(position)] = Some(player_index);
+            }
+        }
+        let board = Board::from_ownership_array(ownership).unwrap();
+        assert_eq!(board.status, Status::HasWinner);
+        assert_eq!(board.get_num_winning_combinations(), 1);
+        assert!(board.has_tris());
+        assert_eq!(board.won_by(), Some(0));
+        let winning_move = board.get_moves()[board.winning_move_index().unwrap()];
+        assert!(['A', 'H', 'G'].contains(&winning_move));
+    }
+
+    #[test]
+    fn from_ownership_array_checks_state_is_consistent() {
+        let mut ownership = [None; 27];
+        // Second player cannot move before first player.
+        ownership[0] = Some(1);
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap_err(),
+            Error::InconsistentState
+        );
+        // There are only three players.
+        ownership[0] = Some(3);
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap_err(),
+            Error::InconsistentState
+        );
+        // First player has a winning combination but it is not the last one moving.
+        for (index, owner) in [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (3, 1),
+            (4, 1),
+            (5, 1),
+            (6, 2),
+            (7, 2),
+        ] {
+            ownership[index] = Some(owner);
+        }
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap_err(),
+            Error::InconsistentState
+        );
+        // First player has two winning combinations with no position in common.
+        let mut ownership = [None; 27];
+        for (positions, player_index) in [("AHGLMN", 0), ("FJPYZ", 1), ("CERUV", 2)] {
+            for position in positions.chars() {
+                ownership[position_index(position)] = Some(player_index);
+            }
+        }
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+
+    #[test]
+    fn from_ownership_array_does_not_tie_early() {
+        let mut board = Board::new();
+        for p in "FAUDWETZQJYBHOIK*SNLXCRPVM".chars() {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.status, Status::Tie);
+        // Playing every player positions in the order of the `POSITION` array
+        // would end in a tie after 25 moves.
+        let rebuilt_board = Board::from_ownership_array(board.ownership()).unwrap();
+        assert_eq!(rebuilt_board.ownership(), board.ownership());
+        assert_eq!(rebuilt_board.status, Status::Tie);
+    }
+
+    #[test]
+    fn can_still_win_works() {
+        let mut board = Board::new();
+        assert!(board.can_still_win(2));
+        for p in [
+            'K', '*', 'S', 'Q', 'G', 'X', 'E', 'O', 'V', 'T', 'N', 'D', 'Z', 'U', 'P', 'C', 'R',
+            'L', 'H', 'Y', 'M', 'J', 'I', 'W',
+        ] {
+            board.add_move(p).unwrap();
+        }
+        // First player needs one more position to complete 'A', 'K', 'T'.
+        assert!(board.can_still_win(0));
+        // Second player needs two more positions to complete 'B', 'I', 'F'
+        // but there is only one move left.
+        assert!(!board.can_still_win(1));
+        // Third player has no combination left.
+        assert!(!board.can_still_win(2));
+    }
+
+    #[test]
+    fn checksum_depends_on_ownership_only() {
+        let mut board_a = Board::new();
+        let mut board_b = Board::new();
+        let mut board_c = Board::new();
+        for p in ['A', 'B', 'C', 'D'] {
+            board_a.add_move(p).unwrap();
+            board_b.add_move(p).unwrap();
+        }
+        for p in ['D', 'B', 'C', 'A'] {
+            board_c.add_move(p).unwrap();
+        }
+        assert_eq!(board_a.checksum(), board_b.checksum());
+        assert_eq!(board_a.checksum(), board_c.checksum());
+        board_b.add_move('E').unwrap();
+        assert_ne!(board_a.checksum(), board_b.checksum());
+        assert_ne!(Board::new().checksum(), board_a.checksum());
+    }
+
+    #[test]
+    fn live_lines_matrix_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H'] {
+            board.add_move(p).unwrap();
+        }
+        let matrix = board.live_lines_matrix();
+        assert_eq!(matrix.len(), 49);
+        for (line, is_live_for) in [
+            // Empty line.
+            (('R', 'Z', 'V'), [true, true, true]),
+            // Line with positions of first player only.
+            (('A', 'H', 'G'), [true, false, false]),
+            // Line with positions of second player only.
+            (('B', 'K', 'S'), [false, true, false]),
+            // Line with positions of every player.
+            (('A', 'B', 'C'), [false, false, false]),
+        ] {
+            assert!(matrix.contains(&(line, is_live_for.to_vec())));
+        }
+    }
+
+    #[test]
+    fn center_lines_status_works() {
+        let mut board = Board::new();
+        for p in ['A', '*', 'V', 'I', 'B'] {
+            board.add_move(p).unwrap();
+        }
+        let center_lines_status = board.center_lines_status();
+        assert_eq!(center_lines_status.len(), 13);
+        for (line, state) in [
+            (('A', '*', 'V'), LineState::Dead),
+            (('I', '*', 'Z'), LineState::Dead),
+            (
+                ('K', '*', 'O'),
+                LineState::Owned {
+                    player_index: 1,
+                    num_positions: 1,
+                },
+            ),
+            (
+                ('B', '*', 'W'),
+                LineState::Owned {
+                    player_index: 1,
+                    num_positions: 2,
+                },
+            ),
+        ] {
+            assert!(center_lines_status.contains(&(line, state)));
+        }
+    }
+
+    #[test]
+    fn game_phases() {
+        let mut board = Board::new();
+        for (num_moves, p) in [
+            'K', '*', 'S', 'Q', 'G', 'X', 'E', 'O', 'V', 'T', 'N', 'D', 'Z', 'U', 'P', 'C', 'R',
+            'L', 'H', 'Y', 'M', 'J', 'I', 'W',
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            assert_eq!(board.is_opening(), num_moves < 3);
+            assert!(!board.is_endgame());
+            board.add_move(p).unwrap();
+        }
+        assert!(!board.is_opening());
+        assert!(board.is_endgame());
+    }
+
+    #[test]
+    fn threats_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H', 'E'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.threats(0), vec!['G']);
+        assert_eq!(board.threats(1), vec![]);
+        board.add_move('*').unwrap();
+        assert_eq!(board.threats(2), vec!['Y']);
+    }
+
+    #[test]
+    fn drop_stacks_positions() {
+        let mut board = Board::new();
+        assert_eq!(board.drop(0, 0), Ok('A'));
+        assert_eq!(board.drop(0, 0), Ok('J'));
+        assert_eq!(board.drop(0, 0), Ok('R'));
+        assert_eq!(board.drop(0, 0), Err(Error::ColumnFull));
+        assert_eq!(board.drop(3, 0), Err(Error::InvalidPosition));
+        assert_eq!(board.get_num_moves(), 3);
+    }
+
+    #[test]
+    fn gravity_rejects_floating_positions() {
+        let mut board = Board::with_gravity();
+        assert_eq!(board.available_positions().len(), 9);
+        assert_eq!(board.add_move('J'), Err(Error::PositionBelowIsEmpty));
+        assert_eq!(board.add_move('*'), Err(Error::PositionBelowIsEmpty));
+        assert_eq!(board.add_move('A'), Ok(0));
+        assert_eq!(board.add_move('R'), Err(Error::PositionBelowIsEmpty));
+        assert_eq!(board.add_move('J'), Ok(0));
+        assert!(board.available_positions().contains(&'R'));
+        assert_eq!(board.drop(1, 1), Ok('I'));
+        assert!(Board::new().add_move('J').is_ok());
+    }
+
+    #[test]
+    fn drop_detects_vertical_win() {
+        let mut board = Board::new();
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 0), (1, 0), (2, 0)] {
+            board.drop(x, y).unwrap();
+        }
+        assert_eq!(board.status, Status::IsPlaying);
+        assert_eq!(board.drop(0, 0), Ok('R'));
+        assert_eq!(board.status, Status::HasWinner);
+    }
+
+    #[test]
+    fn moves_to_complete_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H', 'S', 'E', 'G'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.moves_to_complete(0, ('A', 'H', 'G')), Ok(Some(0)));
+        assert_eq!(board.moves_to_complete(0, ('A', 'J', 'R')), Ok(Some(2)));
+        assert_eq!(board.moves_to_complete(1, ('B', 'K', 'S')), Ok(Some(1)));
+        assert_eq!(board.moves_to_complete(1, ('R', 'Z', 'V')), Ok(Some(3)));
+        assert_eq!(board.moves_to_complete(1, ('A', 'H', 'G')), Ok(None));
+        assert_eq!(board.moves_to_complete(0, ('A', 'B', 'C')), Ok(None));
+        assert_eq!(
+            board.moves_to_complete(0, ('A', 'B', 'D')),
+            Err(Error::NotAWinningCombination)
+        );
+    }
+
+    #[test]
+    fn render_labeled_works() {
+        let mut board = Board::new();
+        board.add_move('A').unwrap();
+        board.add_move('*').unwrap();
+        let rendered = board.render_labeled();
+        let layers: Vec<&str> = rendered.split("\n\n").collect();
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers[0], "T:. U:. V:.\nS:. Z:. W:.\nR:. X:. Y:.");
+        assert_eq!(layers[1], "L:. M:. N:.\nK:. *:1 O:.\nJ:. Q:. P:.");
+        assert_eq!(layers[2], "C:. D:. E:.\nB:. I:. F:.\nA:0 H:. G:.");
+    }
+
+    #[test]
+    fn structure_stats_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H', '*', 'V', 'I', 'Q', 'D', 'Z'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(
+            board.structure_stats(0),
+            StructureStats {
+                corners: 1,
+                edges: 1,
+                face_centers: 2,
+                center: 0,
+            }
+        );
+        assert_eq!(
+            board.structure_stats(1),
+            StructureStats {
+                corners: 0,
+                edges: 1,
+                face_centers: 1,
+                center: 1,
+            }
+        );
+        assert_eq!(
+            board.structure_stats(2),
+            StructureStats {
+                corners: 2,
+                edges: 1,
+                face_centers: 0,
+                center: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn rotations_are_distinct_and_keep_winning_combinations() {
+        let rotations = rotations();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], POSITION);
+        for (i, rotation) in rotations.iter().enumerate() {
+            assert!(!rotations[..i].contains(rotation));
+            for &(a, b, c) in canonical_winning_lines() {
+                let [a, b, c] = [a, b, c].map(|position| rotation[position_index(position)]);
+                assert!(get_is_winning_combination(a, b, c).unwrap());
+            }
+        }
+        // The mirror image is not a rotation.
+        let mirror = POSITION.map(|position| {
+            let (x, y, z) = vector_of_position(position).unwrap();
+            position_of_vector((2 - x, y, z)).unwrap()
+        });
+        assert!(!rotations.contains(&mirror));
+    }
+
+    #[test]
+    fn symmetries_keep_winning_combinations() {
+        let symmetries = symmetries();
+        assert_eq!(symmetries.len(), 48);
+        assert_eq!(symmetries[..24], rotations());
+        for (i, symmetry) in symmetries.iter().enumerate() {
+            assert!(!symmetries[..i].contains(symmetry));
+            for &(a, b, c) in canonical_winning_lines() {
+                let [a, b, c] = [a, b, c].map(|position| symmetry[position_index(position)]);
+                assert!(get_is_winning_combination(a, b, c).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn is_symmetric_to_includes_reflections() {
+        let mut board = Board::new();
+        for position in ['A', 'H', 'B'] {
+            board.add_move(position).unwrap();
+        }
+        let mirror = POSITION.map(|position| {
+            let (x, y, z) = vector_of_position(position).unwrap();
+            position_of_vector((2 - x, y, z)).unwrap()
+        });
+        let mut mirrored_board = Board::new();
+        for &position in board.get_moves() {
+            mirrored_board
+                .add_move(mirror[position_index(position)])
+                .unwrap();
+        }
+        assert!(board.is_symmetric_to(&mirrored_board));
+        assert!(mirrored_board.is_symmetric_to(&board));
+        // No rotation maps the board to its mirror image.
+        for moves in board.augment() {
+            let mut rotated_board = Board::new();
+            for position in moves {
+                rotated_board.add_move(position).unwrap();
+            }
+            assert_ne!(rotated_board.ownership(), mirrored_board.ownership());
+        }
+
+        let mut other_board = Board::new();
+        for position in ['A', 'H', 'C'] {
+            other_board.add_move(position).unwrap();
+        }
+        assert!(!board.is_symmetric_to(&other_board));
+        assert!(!board.is_symmetric_to(&Board::with_num_players(2).unwrap()));
+    }
+
+    #[test]
+    fn distinct_moves_up_to_symmetry_works() {
+        let mut board = Board::new();
+        assert_eq!(board.distinct_moves_up_to_symmetry(), ['A', 'H', 'I', '*']);
+        board.add_move('*').unwrap();
+        assert_eq!(board.distinct_moves_up_to_symmetry(), ['A', 'H', 'I']);
+        // Only the identity leaves this board unchanged.
+        board.undo_move().unwrap();
+        for position in ['A', 'H', 'B'] {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(
+            board.distinct_moves_up_to_symmetry(),
+            board.available_positions()
+        );
+    }
+
+    #[test]
+    fn augment_replays_rotated_boards() {
+        let mut board = Board::new();
+        for position in ['A', 'H', 'G', '*', 'B', 'I', 'V'] {
+            board.add_move(position).unwrap();
+        }
+        let augmented = board.augment();
+        assert_eq!(augmented.len(), 24);
+        assert_eq!(augmented[0], board.get_moves());
+        for (moves, rotation) in augmented.iter().zip(rotations()) {
+            assert_eq!(moves.len(), board.get_num_moves());
+            let mut rotated_board = Board::new();
+            for &position in moves {
+                rotated_board.add_move(position).unwrap();
+            }
+            assert_eq!(rotated_board.status, Status::HasWinner);
+            for (index, position) in POSITION.into_iter().enumerate() {
+                assert_eq!(rotated_board.owner(rotation[index]), board.owner(position));
+            }
+        }
+    }
+
+    #[test]
+    fn move_wins_agrees_with_full_scan() {
+        let mut board = Board::new();
+        for position in ['A', 'H', 'G', '*', 'I', 'B', 'D'] {
+            board.add_move(position).unwrap();
+        }
+        for player_index in 0..3 {
+            for position in POSITION {
+                if board.owner(position).is_some() {
+                    continue;
+                }
+                // Pair the position with every two positions of the player.
+                let positions: Vec<char> = POSITION
+                    .into_iter()
+                    .filter(|&p| board.owner(p) == Some(usize::from(player_index)))
+                    .collect();
+                let mut full_scan = false;
+                for (i, &position_a) in positions.iter().enumerate() {
+                    for &position_b in &positions[i + 1..] {
+                        full_scan |=
+                            get_is_winning_combination(position_a, position_b, position).unwrap();
+                    }
+                }
+                assert_eq!(board.move_wins(position, player_index).unwrap(), full_scan);
+            }
+        }
+        assert!(board.move_wins('V', 0).unwrap());
+        assert!(!board.move_wins('V', 1).unwrap());
+        assert_eq!(
+            board.move_wins('A', 0).unwrap_err(),
+            Error::PositionAlreadyTaken
+        );
+        assert_eq!(board.move_wins('Ç', 0).unwrap_err(), Error::InvalidPosition);
+    }
+
+    #[test]
+    fn threat_proximity_prefers_positions_near_own_lines() {
+        let mut board = Board::new();
+        for position in ['A', 'B', 'C', 'H', 'D', 'E'] {
+            board.add_move(position).unwrap();
+        }
+        // First player took 'A' and 'H', next to 'G'.
+        assert_eq!(board.threat_proximity('G'), 2);
+        assert_eq!(board.threat_proximity('J'), 1);
+        assert_eq!(board.threat_proximity('N'), 0);
+        assert_eq!(board.threat_proximity('A'), 0);
+    }
+
+    #[test]
+    fn compress_round_trip() {
+        let mut boards = vec![Board::new()];
+        for moves in ["V", "CSPIEAKX*LHNOJBYUFQRDVGTZWM"] {
+            let mut board = Board::new();
+            for position in moves.chars() {
+                board.add_move(position).unwrap();
+            }
+            boards.push(board);
+        }
+        assert_eq!(boards[0].compress().len(), 1);
+        assert_eq!(boards[1].compress(), [26, 0b0100_0001]);
+        assert_eq!(boards[2].status, Status::Tie);
+        for board in boards {
+            let decompressed_board = Board::decompress(&board.compress()).unwrap();
+            assert_eq!(decompressed_board.ownership(), board.ownership());
+            assert_eq!(decompressed_board.status, board.status);
+        }
+    }
+
+    #[test]
+    fn decompress_checks_data() {
+        assert_eq!(
+            Board::decompress(&[]).unwrap_err(),
+            Error::InconsistentState
+        );
+        assert_eq!(
+            Board::decompress(&[26]).unwrap_err(),
+            Error::InconsistentState
+        );
+        assert_eq!(
+            Board::decompress(&[27, 1]).unwrap_err(),
+            Error::InconsistentState
+        );
+        // Second player cannot move first.
+        assert_eq!(
+            Board::decompress(&[0b1000_0001, 26]).unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+
+    #[test]
+    fn drawing_moves_leave_nobody_able_to_win() {
+        let mut board = Board::new();
+        for position in "CSPIEAKX*LHNOJBYUFQRDVGTZ".chars() {
+            board.add_move(position).unwrap();
+        }
+        // Only 'M' and 'W' are empty, third player can still win taking 'W'.
+        assert!(board.can_still_win(2));
+        assert_eq!(board.drawing_moves(), ['W']);
+        board.add_move('W').unwrap();
+        assert_eq!(board.drawing_moves(), ['M']);
+        board.add_move('M').unwrap();
+        assert!(board.drawing_moves().is_empty());
+    }
+
+    #[test]
+    fn best_defense_blocks_next_opponent() {
+        let mut board = Board::new();
+        for position in ['X', 'E', 'W', 'P', 'Z', 'Y', 'M'] {
+            board.add_move(position).unwrap();
+        }
+        // Third player would win taking 'V'.
+        assert_eq!(board.threats(2), ['V']);
+        assert_eq!(board.best_defense(1, 2), Some('V'));
+        // Every move looks the same without looking ahead.
+        assert_eq!(board.best_defense(1, 1), Some('A'));
+        assert_eq!(board.best_defense(0, 2), None);
+    }
+
+    #[test]
+    fn undo_move_restores_status() {
+        let mut board = Board::new();
+        assert_eq!(board.undo_move().unwrap_err(), Error::NoMoveToUndo);
+        for position in ['A', 'H', 'G', '*', 'I', 'F', 'V'] {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(board.undo_move(), Ok('V'));
+        assert_eq!(board.status, Status::IsPlaying);
+        assert_eq!(board.get_num_moves(), 6);
+        board.add_move('V').unwrap();
+        assert_eq!(board.status, Status::HasWinner);
+
+        let mut board = Board::new();
+        for position in "CSPIEAKX*LHNOJBYUFQRDVGTZWM".chars() {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(board.status, Status::Tie);
+        assert_eq!(board.undo_move(), Ok('M'));
+        assert_eq!(board.status, Status::IsPlaying);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_boards_are_legal() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        for seed in 0..100_u32 {
+            let data: Vec<u8> = (0..64_u32).map(|i| (seed * 31 + i * 17) as u8).collect();
+            let board = Board::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let mut replayed_board = Board::new();
+            for &position in board.get_moves() {
+                replayed_board.add_move(position).unwrap();
+            }
+            assert_eq!(replayed_board, board);
+            let rebuilt_board = Board::from_ownership_array(board.ownership()).unwrap();
+            assert_eq!(rebuilt_board.status, board.status);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut board = Board::with_num_players(2).unwrap();
+        for position in ['A', 'H', 'G', '*'] {
+            board.add_move(position).unwrap();
+        }
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+        for json in [
+            r#"{"status":"IsPlaying","moves":["A","A"],"num_players":3}"#,
+            r#"{"status":"HasWinner","moves":["A"],"num_players":3}"#,
+            r#"{"status":"IsPlaying","moves":[],"num_players":4}"#,
+        ] {
+            assert!(serde_json::from_str::<Board>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn available_positions_works() {
+        let mut board = Board::new();
+        assert_eq!(board.available_positions(), POSITION);
+        board.add_move('H').unwrap();
+        board.add_move('*').unwrap();
+        let available_positions = board.available_positions();
+        assert_eq!(available_positions.len(), 25);
+        assert_eq!(available_positions[..3], ['A', 'G', 'B']);
+        assert!(!available_positions.contains(&'*'));
+        assert!(board.is_available('A'));
+        assert!(!board.is_available('H'));
+        assert!(!board.is_available(' '));
+
+        let mut board = Board::new();
+        for position in "CSPIEAKX*LHNOJBYUFQRDVGTZWM".chars() {
+            board.add_move(position).unwrap();
+        }
+        assert!(board.available_positions().is_empty());
+    }
+
+    #[test]
+    fn is_available_agrees_with_available_positions() {
+        let mut boards = Vec::new();
+        for moves in ["", "H*", "AHG*IFV", "JQI*BAEURHFNSKTCWPOYVZLD"] {
+            let mut board = Board::new();
+            for position in moves.chars() {
+                board.add_move(position).unwrap();
+            }
+            boards.push(board);
+        }
+        let mut board = Board::with_gravity();
+        board.add_move('A').unwrap();
+        boards.push(board);
+        for board in boards {
+            let available_positions = board.available_positions();
+            for position in POSITION {
+                assert_eq!(
+                    board.is_available(position),
+                    available_positions.contains(&position)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn player_index_at_follows_turns() {
+        let mut board = Board::new();
+        for position in ['A', 'H', 'G', '*'] {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(board.player_index_at('A'), Some(0));
+        assert_eq!(board.player_index_at('H'), Some(1));
+        assert_eq!(board.player_index_at('G'), Some(2));
+        assert_eq!(board.player_index_at('*'), Some(0));
+        assert_eq!(board.player_index_at('B'), None);
+        assert_eq!(board.player_index_at('a'), None);
+    }
+
+    #[test]
+    fn canonical_notation_is_invariant_under_rotations() {
+        let notation = "AHG*IFV";
+        let canonical = canonical_notation(notation).unwrap();
+        assert_eq!(canonical.len(), notation.len());
+        for rotation in rotations() {
+            let rotated: String = notation
+                .chars()
+                .map(|position| rotation[position_index(position)])
+                .collect();
+            assert_eq!(canonical_notation(&rotated).unwrap(), canonical);
+        }
+        assert_ne!(
+            canonical_notation("AB").unwrap(),
+            canonical_notation("AC").unwrap()
+        );
+        assert_eq!(
+            canonical_notation("AA").unwrap_err(),
+            Error::PositionAlreadyTaken
+        );
+        assert_eq!(canonical_notation("a").unwrap_err(), Error::InvalidPosition);
+    }
+
+    #[test]
+    fn ownership_masks_are_disjoint() {
+        let mut board = Board::new();
+        for position in ['A', 'H', 'G', '*', 'I', 'F', 'V'] {
+            board.add_move(position).unwrap();
+        }
+        let masks = [0, 1, 2].map(|player_index| board.ownership_mask(player_index));
+        assert_eq!(masks[0].iter().filter(|&&is_set| is_set).count(), 3);
+        let counts = combine_masks(&masks);
+        assert!(counts.iter().all(|&count| count <= 1));
+        assert_eq!(counts.iter().sum::<u8>(), 7);
+        assert_eq!(combine_masks(&[masks[0], masks[0]])[13], 2);
+        assert_eq!(combine_masks(&[]), [0; 27]);
+    }
+
+    #[test]
+    fn all_forks_lists_threats_created() {
+        let mut board = Board::new();
+        for position in ['A', 'N', 'V', 'U'] {
+            board.add_move(position).unwrap();
+        }
+        // First player took 'A' and 'U'.
+        assert_eq!(
+            board.all_forks(0),
+            [('H', vec![('A', 'H', 'G'), ('H', '*', 'U')])]
+        );
+        assert!(board.all_forks(1).is_empty());
+    }
+
+    #[test]
+    fn suggest_move_prefers_winning_then_blocking() {
+        let mut board = Board::new();
+        assert_eq!(board.suggest_move(), Some('*'));
+        board.add_move('*').unwrap();
+        assert_eq!(board.suggest_move(), Some('A'));
+        for position in ['K', 'Q', 'O', 'C', 'P'] {
+            board.add_move(position).unwrap();
+        }
+        // The next player threatens 'R', the one after threatens 'J'.
+        assert_eq!(board.threats(0), []);
+        assert_eq!(board.threats(1), ['R']);
+        assert_eq!(board.threats(2), ['J']);
+        assert_eq!(board.suggest_move(), Some('R'));
+
+        let mut board = Board::new();
+        for position in ['A', 'H', 'G', '*', 'I', 'F', 'V'] {
+            board.add_move(position).unwrap();
+            if position == 'F' {
+                assert_eq!(board.suggest_move(), Some('V'));
+            }
+        }
+        assert_eq!(board.suggest_move(), None);
+
+        let mut board = Board::with_gravity();
+        assert_eq!(board.suggest_move(), Some('A'));
+        // Player 0 threatens 'J' and 'R', floating above 'A', and 'M'.
+        for position in "EDHQGIPCL*ZX".chars() {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(board.threats(0), ['J', 'M', 'R']);
+        assert_eq!(board.suggest_move(), Some('M'));
+        while let Some(position) = board.suggest_move() {
+            board.add_move(position).unwrap();
+        }
+        assert_ne!(board.status, Status::IsPlaying);
+    }
+
+    #[test]
+    fn with_players_swapped_exchanges_positions() {
+        let mut board = Board::new();
+        for p in "AHG*IF".chars() {
+            board.add_move(p).unwrap();
+        }
+        let swapped_board = board.with_players_swapped(1, 2).unwrap();
+        assert_eq!(swapped_board.player_index_at('H'), Some(2));
+        assert_eq!(swapped_board.player_index_at('G'), Some(1));
+        assert_eq!(
+            swapped_board
+                .with_players_swapped(2, 1)
+                .unwrap()
+                .ownership(),
+            board.ownership()
+        );
+
+        // The first player moved once more than the second one.
+        board.add_move('V').unwrap();
+        assert_eq!(board.won_by(), Some(0));
+        assert_eq!(
+            board.with_players_swapped(0, 1).unwrap_err(),
+            Error::InconsistentState
+        );
+        assert_eq!(board.with_players_swapped(1, 2).unwrap().won_by(), Some(0));
+        assert_eq!(
+            board.with_players_swapped(0, 3).unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+
+    #[test]
+    fn won_by_checks_every_player() {
+        let mut ownership = [None; 27];
+        for (index, owner) in [(0, 0), (1, 0), (2, 0), (3, 1), (4, 2), (5, 1), (7, 2)] {
+            ownership[index] = Some(owner);
+        }
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap().won_by(),
+            Some(0)
+        );
+        ownership[2] = None;
+        ownership[8] = Some(0);
+        assert_eq!(
+            Board::from_ownership_array(ownership).unwrap().won_by(),
+            None
+        );
+
+        // First player completed 'A', 'H', 'G' before others moved again.
+        let board = Board {
+            moves: vec!['A', 'B', 'C', 'H', 'D', 'E', 'G', 'F', 'V'],
+            status: Status::IsPlaying,
+            num_players: 3,
+            gravity: false,
+            exhaustive_win_scan: false,
+        };
+        assert_eq!(board.get_num_winning_combinations(), 0);
+        assert_eq!(board.won_by(), Some(0));
+
+        // Both first and second player have a winning combination.
+        let board = Board {
+            moves: vec!['A', 'B', 'C', 'H', 'I', 'D', 'G', 'F'],
+            status: Status::IsPlaying,
+            num_players: 3,
+            gravity: false,
+            exhaustive_win_scan: false,
+        };
+        assert_eq!(board.won_by(), None);
+    }
+
+    #[test]
+    fn exhaustive_win_scan_finds_wins_away_from_last_move() {
+        // First player completed 'A', 'H', 'G' and then moved again.
+        let board = Board {
+            moves: vec!['A', 'B', 'C', 'H', 'D', 'E', 'G', 'F', 'I', 'V'],
+            status: Status::IsPlaying,
+            num_players: 3,
+            gravity: false,
+            exhaustive_win_scan: false,
+        };
+        assert_eq!(board.get_num_winning_combinations(), 0);
+        let board = board.with_exhaustive_win_scan(true);
+        assert_eq!(board.winning_combinations(), [('A', 'H', 'G')]);
+        assert_eq!(
+            Board::new()
+                .with_exhaustive_win_scan(true)
+                .get_num_winning_combinations(),
+            0
+        );
+    }
+
+    #[test]
+    fn display_draws_layers() {
+        let mut board = Board::new();
+        board.add_move('V').unwrap();
+        board.add_move('*').unwrap();
+        let text = board.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 30);
+        assert_eq!(lines[0], "           ______________________");
+        assert_eq!(lines[1], "         /       /       /       /");
+        assert_eq!(lines[2], "        /   T   /   U   /   0   /");
+        assert_eq!(lines[3], "       /_______/_______/_______/");
+        assert_eq!(lines[15], "     /   K   /   1   /   O   /");
+        assert_eq!(lines[28], "  /   A   /   H   /   G   /");
+        assert_eq!(lines[29], " /_______/_______/_______/");
+    }
+
+    #[test]
+    fn recent_moves_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.recent_moves(3), &['C', '*', 'D']);
+        assert_eq!(board.recent_moves(0), &[] as &[char]);
+        assert_eq!(board.recent_moves(10), &['A', 'B', 'C', '*', 'D']);
+    }
+
+    #[test]
+    fn winning_move_index_points_at_last_move() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D', 'E'] {
+            board.add_move(p).unwrap();
+            assert_eq!(board.winning_move_index(), None);
+        }
+        board.add_move('V').unwrap();
+        assert_eq!(board.winning_move_index(), Some(6));
+    }
+
+    #[test]
+    fn is_line_live_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'H', 'E'] {
+            board.add_move(p).unwrap();
+        }
+        // Empty line.
+        assert!(board.is_line_live(('R', 'Z', 'V')).unwrap());
+        // Line with positions of first player only.
+        assert!(board.is_line_live(('A', 'J', 'R')).unwrap());
+        assert!(board.is_line_live(('A', 'H', 'G')).unwrap());
+        // Line with positions of first and second player.
+        assert!(!board.is_line_live(('A', 'I', 'E')).unwrap());
+    }
+
+    #[test]
+    fn is_line_live_checks_line_is_a_winning_combination() {
+        assert_eq!(
+            Board::new().is_line_live(('A', 'B', 'D')).unwrap_err(),
+            Error::NotAWinningCombination
+        );
+        assert_eq!(
+            Board::new().is_line_live(('A', 'A', 'B')).unwrap_err(),
+            Error::PositionsMustBeDistinct
+        );
+    }
+
+    #[test]
+    fn shared_cell_of_double_win_works() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', 'G', 'F', 'E', 'T', 'S', 'R', 'V', 'W', 'Y'] {
+            board.add_move(p).unwrap();
+            assert_eq!(board.shared_cell_of_double_win(), None);
+        }
+        board.add_move('*').unwrap();
+        assert_eq!(board.shared_cell_of_double_win(), Some('*'));
+    }
+
+    #[test]
+    fn shared_cell_of_double_win_is_none_for_single_win() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D', 'E', 'V'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.shared_cell_of_double_win(), None);
+    }
+
+    #[test]
+    fn playing_results() {
+        for (positions, status, num_winning_combinations) in [
+            // First move.
+            (vec!['A'], Status::IsPlaying, 0),
+            (
+                vec!['A', 'B', 'C', '*', 'D', 'E', 'G', 'H'],
+                Status::IsPlaying,
+                0,
+            ),
+            // Player one wins will 'A', '*', 'V'.
+            (
+                vec!['A', 'B', 'C', '*', 'D', 'E', 'V'],
+                Status::HasWinner,
+                1,
+            ),
+            // Two winning combinations.
             (
                 vec![
                     'A', 'B', 'C', 'G', 'F', 'E', 'T', 'S', 'R', 'V', 'W', 'Y', '*',
@@ -280,15 +2472,21 @@ mod tests {
                 Status::HasWinner,
                 2,
             ),
-            // Board filled with all positions, no winner.
+            // Nobody can win anymore, with one position left.
             (
                 vec![
                     '*', 'A', 'B', 'V', 'W', 'C', 'D', 'Y', 'X', 'E', 'F', 'R', 'S', 'G', 'H', 'T',
-                    'U', 'P', 'J', 'N', 'L', 'O', 'K', 'M', 'Q', 'Z', 'I',
+                    'U', 'P', 'J', 'N', 'L', 'O', 'K', 'M', 'Q', 'Z',
                 ],
                 Status::Tie,
                 0,
             ),
+            // Board filled with all positions, no winner.
+            (
+                "CSPIEAKX*LHNOJBYUFQRDVGTZWM".chars().collect(),
+                Status::Tie,
+                0,
+            ),
         ] {
             let mut board = Board::default();
             for p in positions {
@@ -301,4 +2499,97 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn cell_weights_favor_the_center() {
+        let weights = Board::cell_weights();
+        assert_eq!(weights[position_index('*')], 13);
+        assert_eq!(weights[position_index('A')], 7);
+        assert_eq!(weights[position_index('H')], 4);
+        assert_eq!(
+            weights.iter().map(|&weight| u32::from(weight)).sum::<u32>(),
+            49 * 3
+        );
+    }
+
+    #[test]
+    fn draw_is_detected_before_board_is_full() {
+        let mut board = Board::new();
+        for position in "JQI*BAEURHFNSKTCWPOYVZL".chars() {
+            board.add_move(position).unwrap();
+        }
+        assert!(!board.is_draw_inevitable());
+        assert_eq!(board.status, Status::IsPlaying);
+        board.add_move('D').unwrap();
+        assert!(board.is_draw_inevitable());
+        assert_eq!(board.status, Status::Tie);
+        assert!(board.available_positions().is_empty());
+        assert_eq!(board.add_move('G').unwrap_err(), Error::GameIsTied);
+        assert_eq!(
+            Board::from_ownership_array(board.ownership())
+                .unwrap()
+                .status,
+            Status::Tie
+        );
+        assert!(!Board::new().is_draw_inevitable());
+
+        let mut board = Board::new();
+        for position in "CSPIEAKX*LHNOJBYUFQRDVGTZWM".chars() {
+            board.add_move(position).unwrap();
+        }
+        assert_eq!(board.add_move('A').unwrap_err(), Error::BoardIsFull);
+    }
+
+    #[test]
+    fn two_players_alternate() {
+        assert_eq!(
+            Board::with_num_players(4).unwrap_err(),
+            Error::InvalidNumberOfPlayers
+        );
+        let mut board = Board::with_num_players(2).unwrap();
+        for position in ['A', 'H', '*', 'G'] {
+            assert_eq!(board.add_move(position), Ok(0));
+            assert_eq!(board.is_opening(), board.get_num_moves() < 2);
+        }
+        assert_eq!(board.threats(0), ['V']);
+        assert!(board
+            .live_lines_matrix()
+            .iter()
+            .all(|(_, is_live_for)| is_live_for.len() == 2));
+        let decompressed_board = Board::decompress(&board.compress()).unwrap();
+        assert_eq!(decompressed_board.num_players(), 2);
+        assert_eq!(decompressed_board.ownership(), board.ownership());
+        assert_eq!(board.add_move('V'), Ok(1));
+        assert_eq!(board.status, Status::HasWinner);
+        assert_eq!(
+            board
+                .ownership_mask(0)
+                .iter()
+                .filter(|&&is_set| is_set)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn winning_combinations_lists_positions() {
+        let mut board = Board::new();
+        for p in ['A', 'B', 'C', '*', 'D', 'E', 'V'] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(board.winning_combinations(), [('A', '*', 'V')]);
+
+        let mut board = Board::new();
+        for p in [
+            'A', 'B', 'C', 'G', 'F', 'E', 'T', 'S', 'R', 'V', 'W', 'Y', '*',
+        ] {
+            board.add_move(p).unwrap();
+        }
+        assert_eq!(
+            board.winning_combinations(),
+            [('A', 'V', '*'), ('G', 'T', '*')]
+        );
+        board.undo_move().unwrap();
+        assert!(board.winning_combinations().is_empty());
+    }
 }
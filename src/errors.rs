@@ -1,14 +1,25 @@
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error {
     BoardIsFull,
     CannotAddMoreThanThreePlayers,
+    CannotAddMoreThanTwoPlayers,
     CannotAddSamePlayerTwice,
+    ColumnFull,
     GameIsOver,
+    GameIsTied,
     GameNotStartedYet,
+    InconsistentState,
+    InvalidLayer,
+    InvalidNumberOfPlayers,
     InvalidPosition,
+    NoMoveToUndo,
+    NotAWinningCombination,
+    OpeningRuleViolation,
     PlayerMustWaitForTurn,
     PlayerNotFound,
     PositionAlreadyTaken,
+    PositionBelowIsEmpty,
     PositionsMustBeDistinct,
     ThereIsAlreadyAWinner,
 }
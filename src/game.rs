@@ -1,30 +1,306 @@
-use crate::board::{Board, Status as BoardStatus};
+use crate::board::{Board, Status as BoardStatus, POSITION};
 use crate::errors::Error;
+use crate::position::vector_of_position;
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
     WaitingForPlayers,
     IsPlaying,
     IsOver,
 }
 
+/// Receive notifications about the moves of a [Game].
+/// Register it with [`Game::add_observer`].
+pub trait GameObserver: Send {
+    /// Called after the player with the given index took a position.
+    fn on_move(&mut self, _player_index: u8, _position: char) {}
+
+    /// Called after the player with the given index won the game.
+    fn on_win(&mut self, _player_index: u8) {}
+
+    /// Called after the game ended in a tie.
+    fn on_tie(&mut self) {}
+}
+
+/// What the last move of a [Game] achieved, see [`Game::classify_last_move`].
+#[derive(Debug, PartialEq)]
+pub enum MoveClass {
+    /// It completed a winning combination.
+    Won,
+    /// It added a position that would complete a winning combination for
+    /// the player who moved.
+    CreatedThreat,
+    /// It took a position that would complete a winning combination for an
+    /// opponent.
+    Blocked,
+    /// None of the above.
+    Quiet,
+}
+
+/// How a move of a [Game] compares with [`Board::best_defense`], see
+/// [`Game::annotate`].
+#[derive(Debug, PartialEq)]
+pub struct MoveAnnotation {
+    pub position: char,
+    /// The move scores as well as the recommended one.
+    pub was_best: bool,
+    /// The player could have completed a winning combination but did not.
+    pub missed_win: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Game {
     board: Board,
+    forbid_center_opening: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observers: Vec<Box<dyn GameObserver>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_turn_start: Option<Box<dyn FnMut(u8) + Send>>,
     player_ids: Vec<String>,
+    // Number of times players were rotated by `next_round`, modulo the number
+    // of players.
+    rotation: usize,
+    series_score: [u32; 3],
     pub status: Status,
 }
 
+/// Configure the rules of a [Game] before creating it.
+///
+/// ```
+/// let game = tris3d::game::GameBuilder::new()
+///     .forbid_center_opening(true)
+///     .exhaustive_win_scan(true)
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct GameBuilder {
+    exhaustive_win_scan: bool,
+    forbid_center_opening: bool,
+}
+
+impl GameBuilder {
+    /// Start from the standard rules.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forbid taking the center as the very first move.
+    #[must_use]
+    pub fn forbid_center_opening(mut self, forbid: bool) -> Self {
+        self.forbid_center_opening = forbid;
+        self
+    }
+
+    /// Check every winning combination after each move, see
+    /// [`Board::with_exhaustive_win_scan`].
+    #[must_use]
+    pub fn exhaustive_win_scan(mut self, exhaustive: bool) -> Self {
+        self.exhaustive_win_scan = exhaustive;
+        self
+    }
+
+    /// Create a new game with the configured rules.
+    #[must_use]
+    pub fn build(self) -> Game {
+        Game {
+            board: Board::new().with_exhaustive_win_scan(self.exhaustive_win_scan),
+            forbid_center_opening: self.forbid_center_opening,
+            ..Game::new()
+        }
+    }
+}
+
 impl Game {
     /// Create an new game, with no players and an empty board.
     #[must_use]
     pub fn new() -> Self {
         Self {
             board: Board::new(),
+            forbid_center_opening: false,
+            observers: Vec::new(),
+            on_turn_start: None,
             player_ids: Vec::new(),
+            rotation: 0,
+            series_score: [0; 3],
             status: Status::WaitingForPlayers,
         }
     }
 
+    /// Create a new game for the given number of players, either 2 or 3.
+    /// Players move in turn like in the three player game.
+    ///
+    /// ```
+    /// let game = tris3d::game::Game::with_player_count(2).unwrap();
+    /// ```
+    pub fn with_player_count(num_players: usize) -> Result<Self, Error> {
+        Ok(Self {
+            board: Board::with_num_players(num_players)?,
+            ..Self::new()
+        })
+    }
+
+    /// Create a game with the given players and replay the given moves.
+    /// The notation is the sequence of positions played, for instance `"AHG*IFV"`.
+    ///
+    /// ```
+    /// let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG").unwrap();
+    /// ```
+    pub fn quick_start(player_ids: [&str; 3], notation: &str) -> Result<Self, Error> {
+        Self::resume(player_ids.map(String::from).into(), notation)
+    }
+
+    /// Add the given players and replay the given moves, leaving the game in
+    /// progress, won or tied depending on the moves, like [`Game::quick_start`].
+    /// The game is for as many players as player ids, either 2 or 3.
+    ///
+    /// ```
+    /// # use tris3d::game::Game;
+    /// let players = vec![String::from("Alice"), String::from("Bob"), String::from("Neuromancer")];
+    /// let game = Game::resume(players, "AHG*I").unwrap();
+    /// assert_eq!(game.current_player_index(), Some(2));
+    /// ```
+    pub fn resume(player_ids: Vec<String>, notation: &str) -> Result<Self, Error> {
+        Self::replay(player_ids, notation.chars())
+    }
+
+    /// Create a game with the given players and play the given moves in
+    /// order, like [`Game::quick_start`].
+    ///
+    /// ```
+    /// # use tris3d::game::Game;
+    /// let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+    /// let game = Game::from_moves(player_ids, &['A', 'H', 'G']).unwrap();
+    /// assert_eq!(game.moves(), ['A', 'H', 'G']);
+    /// ```
+    pub fn from_moves(player_ids: [String; 3], moves: &[char]) -> Result<Self, Error> {
+        Self::replay(player_ids.into(), moves.iter().copied())
+    }
+
+    // Add the given players and play the given moves in order.
+    fn replay(
+        player_ids: Vec<String>,
+        moves: impl IntoIterator<Item = char>,
+    ) -> Result<Self, Error> {
+        let mut game = Self::with_player_count(player_ids.len())?;
+        for player_id in player_ids {
+            game.add_player(player_id)?;
+        }
+        for position in moves {
+            let Some(player_index) = game.current_player_index() else {
+                return Err(if game.status == Status::IsOver {
+                    Error::GameIsOver
+                } else {
+                    Error::GameNotStartedYet
+                });
+            };
+            let player_id = game.player_ids[usize::from(player_index)].clone();
+            game.add_move(player_id, position)?;
+        }
+        Ok(game)
+    }
+
+    /// Encode the game state in a fixed size packet, player ids excluded.
+    ///
+    /// The first byte holds the game status in its lowest two bits
+    /// (`0` waiting for players, `1` playing, `2` over) and the index of the
+    /// current player in the next two bits (`3` if there is none).
+    /// The following seven bytes hold two bits per position, in the order of
+    /// the `POSITION` array starting from the lowest bits: `0` for an empty
+    /// position, otherwise one plus the index of the player who took it.
+    /// The two highest bits of the last byte are `1` for a two player game.
+    ///
+    /// Move order and game rules are not encoded.
+    #[must_use]
+    pub fn to_compact_state(&self) -> [u8; 8] {
+        let mut state = [0; 8];
+        state[0] = match self.status {
+            Status::WaitingForPlayers => 0,
+            Status::IsPlaying => 1,
+            Status::IsOver => 2,
+        };
+        state[0] |= self.current_player_index().unwrap_or(3) << 2;
+        for (index, owner) in self.board.ownership().into_iter().enumerate() {
+            if let Some(player_index) = owner {
+                state[1 + index / 4] |= (player_index + 1) << (2 * (index % 4));
+            }
+        }
+        if self.board.num_players() == 2 {
+            state[7] |= 1 << 6;
+        }
+        state
+    }
+
+    /// Create a game from the given players and the packet produced by
+    /// [`Game::to_compact_state`].
+    pub fn from_compact_state(player_ids: Vec<String>, state: [u8; 8]) -> Result<Self, Error> {
+        let num_players = match state[7] >> 6 {
+            0 => 3,
+            1 => 2,
+            _ => return Err(Error::InconsistentState),
+        };
+        let mut game = Self::with_player_count(num_players)?;
+        for player_id in player_ids {
+            game.add_player(player_id)?;
+        }
+        let mut ownership = [None; 27];
+        for (index, owner) in ownership.iter_mut().enumerate() {
+            let cell = (state[1 + index / 4] >> (2 * (index % 4))) & 0b11;
+            if cell > 0 {
+                *owner = Some(cell - 1);
+            }
+        }
+        game.board = Board::from_ownership(ownership, num_players)?;
+        if game.board.get_num_moves() > 0 {
+            if game.status == Status::WaitingForPlayers {
+                return Err(Error::InconsistentState);
+            }
+            if game.board.status != BoardStatus::IsPlaying {
+                game.status = Status::IsOver;
+            }
+        }
+        if game.to_compact_state() != state {
+            return Err(Error::InconsistentState);
+        }
+        Ok(game)
+    }
+
+    /// Create a game from the given players and data in any format the
+    /// crate can decode, telling them apart as follows:
+    ///
+    /// - eight bytes starting with a byte lower than 16 are a packet produced
+    ///   by [`Game::to_compact_state`], since it only uses the lowest four
+    ///   bits of its first byte;
+    /// - with the `serde` feature, data starting with `{` is a game serialized
+    ///   to JSON, whose player ids must be the given ones;
+    /// - otherwise data is read as notation, see [`Game::resume`].
+    ///
+    /// ```
+    /// # use tris3d::game::Game;
+    /// let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+    /// let game = Game::load(player_ids.to_vec(), b"AHG").unwrap();
+    /// assert_eq!(game.moves(), ['A', 'H', 'G']);
+    /// ```
+    pub fn load(player_ids: Vec<String>, data: &[u8]) -> Result<Self, Error> {
+        #[cfg(feature = "serde")]
+        if data.first() == Some(&b'{') {
+            let game: Self = serde_json::from_slice(data).map_err(|_| Error::InconsistentState)?;
+            if game.player_ids != player_ids {
+                return Err(Error::InconsistentState);
+            }
+            return Ok(game);
+        }
+        if let Ok(state) = <[u8; 8]>::try_from(data) {
+            if state[0] < 16 {
+                return Self::from_compact_state(player_ids, state);
+            }
+        }
+        let notation = std::str::from_utf8(data).map_err(|_| Error::InvalidPosition)?;
+        Self::resume(player_ids, notation)
+    }
+
     /// Add a player to the game.
     ///
     /// ```
@@ -32,21 +308,27 @@ impl Game {
     /// game.add_player(String::from("Alice")).unwrap();
     /// ```
     pub fn add_player(&mut self, player_id: String) -> Result<(), Error> {
-        if self.num_players() == 3 {
-            return Err(Error::CannotAddMoreThanThreePlayers);
+        if self.num_players() == self.board.num_players() {
+            return Err(if self.num_players() == 2 {
+                Error::CannotAddMoreThanTwoPlayers
+            } else {
+                Error::CannotAddMoreThanThreePlayers
+            });
         }
         if self.player_ids.contains(&player_id) {
             return Err(Error::CannotAddSamePlayerTwice);
         }
         self.player_ids.push(player_id);
-        if self.num_players() == 3 {
+        if self.num_players() == self.board.num_players() {
             self.status = Status::IsPlaying;
+            self.start_turn();
         }
         Ok(())
     }
 
     /// Add a move to the board.
     /// Return the number of winning combinations.
+    /// On error, the game is left unchanged.
     ///
     /// ```
     /// # let mut game = tris3d::new_game();
@@ -56,163 +338,1661 @@ impl Game {
     /// let num_winning_combinations = game.add_move(String::from("Alice"), 'A').unwrap();
     /// ```
     pub fn add_move(&mut self, player_id: String, position: char) -> Result<u8, Error> {
+        let next_player_index = self.validate_move(&player_id, position)?;
+        let num = self.board.add_move(position)?;
+        let player_index = next_player_index as u8;
+        for observer in &mut self.observers {
+            observer.on_move(player_index, position);
+        }
+        match self.board.status {
+            BoardStatus::IsPlaying => self.start_turn(),
+            BoardStatus::HasWinner => {
+                self.series_score
+                    [(next_player_index + self.rotation) % self.board.num_players()] += 1;
+                for observer in &mut self.observers {
+                    observer.on_win(player_index);
+                }
+                self.status = Status::IsOver;
+            }
+            BoardStatus::Tie => {
+                for observer in &mut self.observers {
+                    observer.on_tie();
+                }
+                self.status = Status::IsOver;
+            }
+        }
+        Ok(num)
+    }
+
+    /// Take back the last move, which only the player who played it can do.
+    /// Return the position it took.
+    ///
+    /// ```
+    /// # let mut game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AH").unwrap();
+    /// assert_eq!(game.undo_move(String::from("Bob")), Ok('H'));
+    /// ```
+    pub fn undo_move(&mut self, player_id: String) -> Result<char, Error> {
         if self.status == Status::WaitingForPlayers {
             return Err(Error::GameNotStartedYet);
         }
-        if self.status == Status::IsOver {
-            return Err(Error::GameIsOver);
-        }
         if !self.player_ids.contains(&player_id) {
             return Err(Error::PlayerNotFound);
         }
-        let next_player_index = self.board.get_num_moves() % 3;
-        if player_id != self.player_ids[next_player_index] {
+        let Some(last_player_index) = self
+            .board
+            .get_num_moves()
+            .checked_sub(1)
+            .map(|i| i % self.board.num_players())
+        else {
+            return Err(Error::NoMoveToUndo);
+        };
+        if player_id != self.player_ids[last_player_index] {
             return Err(Error::PlayerMustWaitForTurn);
         }
-        match self.board.add_move(position) {
-            Ok(num) => {
-                if self.board.status != BoardStatus::IsPlaying {
-                    self.status = Status::IsOver;
-                }
-                Ok(num)
-            }
-            Err(error) => Err(error),
-        }
+        let position = self.take_back()?;
+        self.start_turn();
+        Ok(position)
     }
 
-    #[must_use]
-    pub fn num_players(&self) -> usize {
-        self.player_ids.len()
+    /// Take back moves until reaching the most recent earlier position where
+    /// some player could complete a winning combination with one move.
+    /// Return the number of moves taken back, or `Err(Error::NoMoveToUndo)`
+    /// leaving the game unchanged if there is no such position.
+    ///
+    /// ```
+    /// # let mut game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "ABCHGN").unwrap();
+    /// assert_eq!(game.undo_to_last_threat(), Ok(2));
+    /// ```
+    pub fn undo_to_last_threat(&mut self) -> Result<usize, Error> {
+        let mut board = self.board.clone();
+        let mut num_moves = 0;
+        loop {
+            board.undo_move()?;
+            num_moves += 1;
+            if (0..board.num_players() as u8)
+                .any(|player_index| !board.threats(player_index).is_empty())
+            {
+                break;
+            }
+        }
+        for _ in 0..num_moves {
+            self.take_back()?;
+        }
+        self.start_turn();
+        Ok(num_moves)
     }
-}
 
-impl Default for Game {
-    fn default() -> Self {
-        Self::new()
+    // Take back the last move, whoever played it.
+    fn take_back(&mut self) -> Result<char, Error> {
+        let num_moves = self.board.get_num_moves();
+        if self.board.status == BoardStatus::HasWinner {
+            self.series_score[(num_moves - 1 + self.rotation) % self.board.num_players()] -= 1;
+        }
+        let position = self.board.undo_move()?;
+        self.status = Status::IsPlaying;
+        Ok(position)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn new_game_is_waiting_for_players() {
-        assert_eq!(Game::new().num_players(), 0);
-        assert_eq!(Game::new().status, Status::WaitingForPlayers);
+    /// Check that the given player can play the given position, without
+    /// playing it. Return the same error [`Game::add_move`] would.
+    ///
+    /// ```
+    /// # let mut game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "A").unwrap();
+    /// assert!(game.explain_move("Bob", 'H').is_ok());
+    /// assert!(game.explain_move("Bob", 'A').is_err());
+    /// ```
+    pub fn explain_move(&self, player_id: &str, position: char) -> Result<(), Error> {
+        self.validate_move(player_id, position).map(|_| ())
     }
 
-    #[test]
-    fn add_player_increments_num_players() {
-        let mut game = Game::new();
-        match game.add_player(String::from("player 1")) {
-            Ok(_) => assert_eq!(game.num_players(), 1),
-            Err(_) => assert!(false),
+    // Run every check of a move, in order.
+    // Return the index of the player in turn.
+    fn validate_move(&self, player_id: &str, position: char) -> Result<usize, Error> {
+        if self.status == Status::WaitingForPlayers {
+            return Err(Error::GameNotStartedYet);
         }
-        match game.add_player(String::from("player 2")) {
-            Ok(_) => assert_eq!(game.num_players(), 2),
-            Err(_) => assert!(false),
+        if self.status == Status::IsOver {
+            return Err(Error::GameIsOver);
         }
-        match game.add_player(String::from("player 3")) {
-            Ok(_) => assert_eq!(game.num_players(), 3),
-            Err(_) => assert!(false),
+        if !self.player_ids.iter().any(|id| id == player_id) {
+            return Err(Error::PlayerNotFound);
+        }
+        let next_player_index = self.board.get_num_moves() % self.board.num_players();
+        if player_id != self.player_ids[next_player_index] {
+            return Err(Error::PlayerMustWaitForTurn);
         }
+        if self.forbid_center_opening && position == '*' && self.board.get_num_moves() == 0 {
+            return Err(Error::OpeningRuleViolation);
+        }
+        self.board.validate_move(position)?;
+        Ok(next_player_index)
     }
 
-    #[test]
-    fn add_player_checks_it_was_not_already_added() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
+    /// Start a new round, with an empty board and the second player in turn
+    /// order moving first.
+    ///
+    /// ```
+    /// # let mut game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+    /// game.next_round();
+    /// assert_eq!(game.players_iter().next().unwrap().1, "Bob");
+    /// ```
+    pub fn next_round(&mut self) {
+        self.board.clear();
+        if self.num_players() == self.board.num_players() {
+            self.player_ids.rotate_left(1);
+            self.rotation = (self.rotation + 1) % self.board.num_players();
+            self.status = Status::IsPlaying;
+            self.start_turn();
+        }
+    }
 
-        assert_eq!(
-            game.add_player(String::from("Alice")).unwrap_err(),
-            Error::CannotAddSamePlayerTwice
-        );
+    /// Number of rounds won by every player, in the order players were added.
+    #[must_use]
+    pub fn series_score(&self) -> [u32; 3] {
+        self.series_score
     }
 
-    #[test]
-    fn add_player_does_not_add_more_players_than_allowed() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
-        game.add_player(String::from("Neuromancer")).unwrap();
+    /// Set a function to call with the index of the player expected to move,
+    /// whenever a new turn begins.
+    ///
+    /// ```
+    /// # let mut game = tris3d::new_game();
+    /// game.on_turn_start(|player_index| println!("Player {player_index} to move"));
+    /// ```
+    pub fn on_turn_start(&mut self, f: impl FnMut(u8) + Send + 'static) {
+        self.on_turn_start = Some(Box::new(f));
+    }
 
-        assert_eq!(
-            game.add_player(String::from("Another player")).unwrap_err(),
-            Error::CannotAddMoreThanThreePlayers
-        );
+    /// Register an observer, notified after every move in registration order.
+    pub fn add_observer(&mut self, observer: impl GameObserver + 'static) {
+        self.observers.push(Box::new(observer));
     }
 
-    #[test]
-    fn add_move_checks_that_game_is_not_waiting_for_players() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
+    fn start_turn(&mut self) {
+        if let (Some(player_index), Some(on_turn_start)) =
+            (self.current_player_index(), &mut self.on_turn_start)
+        {
+            on_turn_start(player_index);
+        }
+    }
 
-        assert_eq!(
-            game.add_move(String::from("Alice"), 'A').unwrap_err(),
-            Error::GameNotStartedYet
-        );
+    /// Index of the player expected to move, in turn order.
+    /// Return `None` if the game is not playing.
+    #[must_use]
+    pub fn current_player_index(&self) -> Option<u8> {
+        if self.status == Status::IsPlaying {
+            Some((self.board.get_num_moves() % self.board.num_players()) as u8)
+        } else {
+            None
+        }
     }
 
-    #[test]
-    fn add_move_checks_that_player_is_playing() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
-        game.add_player(String::from("Neuromancer")).unwrap();
+    /// Number of moves left to play before the next one can win, since no
+    /// player can complete a winning combination before their third move.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG").unwrap();
+    /// assert_eq!(game.moves_until_win_possible(), 3);
+    /// ```
+    #[must_use]
+    pub fn moves_until_win_possible(&self) -> usize {
+        (2 * self.board.num_players()).saturating_sub(self.board.get_num_moves())
+    }
 
-        assert_eq!(
-            game.add_move(String::from("Alice"), ' ').unwrap_err(),
-            Error::InvalidPosition
-        );
+    /// Positions played in the current round, in order.
+    #[must_use]
+    pub fn moves(&self) -> &[char] {
+        self.board.get_moves()
     }
 
-    #[test]
-    fn add_move_checks_that_player_turn_is_correct() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
-        game.add_player(String::from("Neuromancer")).unwrap();
+    /// Board of the current round.
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
 
-        assert_eq!(
-            game.add_move(String::from("Bob"), 'A').unwrap_err(),
-            Error::PlayerMustWaitForTurn
-        );
+    /// Check if the boards of the two games are equal up to a rotation or a
+    /// reflection of the cube, see [`Board::is_symmetric_to`]. Player ids
+    /// are not compared.
+    ///
+    /// ```
+    /// # use tris3d::game::Game;
+    /// let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+    /// let mirrored_game = Game::quick_start(["Carol", "Dave", "Eve"], "GHA*IBT").unwrap();
+    /// assert!(game.is_symmetric_to(&mirrored_game));
+    /// ```
+    #[must_use]
+    pub fn is_symmetric_to(&self, other: &Game) -> bool {
+        self.board.is_symmetric_to(&other.board)
     }
 
-    #[test]
-    fn add_move_checks_that_position_is_valid() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
-        game.add_player(String::from("Neuromancer")).unwrap();
+    /// Id of the player expected to move.
+    /// Return `None` if the game is not playing.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "A").unwrap();
+    /// assert_eq!(game.current_player().unwrap(), "Bob");
+    /// ```
+    #[must_use]
+    pub fn current_player(&self) -> Option<&String> {
+        let player_index = self.current_player_index()?;
+        self.player_ids.get(usize::from(player_index))
+    }
 
-        assert_eq!(
-            game.add_move(String::from("Another player"), 'A')
-                .unwrap_err(),
-            Error::PlayerNotFound
-        );
+    /// Id of the player who took the given position.
+    /// Return `None` if the position is free or invalid.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AH").unwrap();
+    /// assert_eq!(game.player_id_at('H').unwrap(), "Bob");
+    /// assert_eq!(game.player_id_at('G'), None);
+    /// ```
+    #[must_use]
+    pub fn player_id_at(&self, position: char) -> Option<&String> {
+        let player_index = self.board.player_index_at(position)?;
+        self.player_ids.get(player_index)
     }
 
-    #[test]
-    fn add_move_checks_if_game_is_over() {
-        let mut game = Game::new();
-        game.add_player(String::from("Alice")).unwrap();
-        game.add_player(String::from("Bob")).unwrap();
-        game.add_player(String::from("Neuromancer")).unwrap();
+    /// Id of the player who completed a winning combination.
+    /// Return `None` while playing or if the game ended in a tie.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+    /// assert_eq!(game.winner().unwrap(), "Alice");
+    /// ```
+    #[must_use]
+    pub fn winner(&self) -> Option<&String> {
+        // No move is accepted once there is a winner, so it is the last one.
+        let winning_move_index = self.board.winning_move_index()?;
+        self.player_ids
+            .get(winning_move_index % self.board.num_players())
+    }
 
-        game.add_move(String::from("Alice"), 'A').unwrap();
-        game.add_move(String::from("Bob"), 'H').unwrap();
-        game.add_move(String::from("Neuromancer"), 'G').unwrap();
-        game.add_move(String::from("Alice"), '*').unwrap();
-        game.add_move(String::from("Bob"), 'I').unwrap();
-        game.add_move(String::from("Neuromancer"), 'F').unwrap();
-        game.add_move(String::from("Alice"), 'V').unwrap();
+    /// One line summary of the game, for example `Move 5 — Bob to play` or
+    /// `Game over — Alice wins (2 lines)`.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHGB").unwrap();
+    /// assert_eq!(game.status_line(), "Move 5 — Bob to play");
+    /// ```
+    #[must_use]
+    pub fn status_line(&self) -> String {
+        let num_moves = self.board.get_num_moves();
+        match self.status {
+            Status::WaitingForPlayers => {
+                format!(
+                    "Waiting for players ({}/{})",
+                    self.num_players(),
+                    self.board.num_players()
+                )
+            }
+            Status::IsPlaying => {
+                format!(
+                    "Move {} — {} to play",
+                    num_moves + 1,
+                    self.player_ids[num_moves % self.board.num_players()]
+                )
+            }
+            Status::IsOver => {
+                if self.board.status == BoardStatus::HasWinner {
+                    let num_winning_combinations = self.board.get_num_winning_combinations();
+                    format!(
+                        "Game over — {} wins ({num_winning_combinations} {})",
+                        self.player_ids[(num_moves - 1) % self.board.num_players()],
+                        if num_winning_combinations == 1 {
+                            "line"
+                        } else {
+                            "lines"
+                        }
+                    )
+                } else {
+                    String::from("Game over — tie")
+                }
+            }
+        }
+    }
+
+    /// Group played positions by layer, that is by their `z` coordinate.
+    /// Every layer lists its positions in move order.
+    ///
+    /// ```
+    /// # let mut game = tris3d::new_game();
+    /// # game.add_player(String::from("Alice")).unwrap();
+    /// # game.add_player(String::from("Bob")).unwrap();
+    /// # game.add_player(String::from("Neuromancer")).unwrap();
+    /// game.add_move(String::from("Alice"), 'V').unwrap();
+    /// let [first_layer, second_layer, third_layer] = game.moves_grouped_by_layer();
+    /// assert_eq!(third_layer, vec!['V']);
+    /// ```
+    #[must_use]
+    pub fn moves_grouped_by_layer(&self) -> [Vec<char>; 3] {
+        let mut layers: [Vec<char>; 3] = Default::default();
+        for &position in self.board.get_moves() {
+            if let Some((_, _, z)) = vector_of_position(position) {
+                layers[usize::from(z)].push(position);
+            }
+        }
+        layers
+    }
+
+    /// Suggest the most urgent move for the current player: a position that
+    /// wins the game if any, otherwise a position that blocks the next
+    /// opponents from winning.
+    /// Return `None` if there is no such position or the game is not playing.
+    #[must_use]
+    pub fn best_tactical_move(&self) -> Option<char> {
+        let player_index = self.current_player_index()?;
+        let num_players = self.board.num_players() as u8;
+        (0..num_players).find_map(|i| {
+            self.board
+                .threats((player_index + i) % num_players)
+                .first()
+                .copied()
+        })
+    }
+
+    /// Play the moves of every player but the given one, for a practice game
+    /// against the computer, until it is the given player turn or the game is
+    /// over. Bots play [`Board::suggest_move`], or the best of
+    /// [`Game::available_ranked`] if the opening rule forbids it.
+    /// Return the positions played, in order.
+    ///
+    /// ```
+    /// # let mut game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "").unwrap();
+    /// game.add_move(String::from("Alice"), 'A').unwrap();
+    /// assert_eq!(game.step_with_bots("Alice").unwrap(), ['*', 'H']);
+    /// ```
+    pub fn step_with_bots(&mut self, human_id: &str) -> Result<Vec<char>, Error> {
+        if self.status == Status::WaitingForPlayers {
+            return Err(Error::GameNotStartedYet);
+        }
+        if !self.player_ids.iter().any(|id| id == human_id) {
+            return Err(Error::PlayerNotFound);
+        }
+        let mut positions = Vec::new();
+        while let Some(player_id) = self.current_player().cloned() {
+            if player_id == human_id {
+                break;
+            }
+            let position = self
+                .board
+                .suggest_move()
+                .filter(|&position| self.validate_move(&player_id, position).is_ok())
+                .or_else(|| {
+                    self.available_ranked()
+                        .first()
+                        .map(|&(position, _)| position)
+                })
+                .ok_or(Error::GameIsOver)?;
+            self.add_move(player_id, position)?;
+            positions.push(position);
+        }
+        Ok(positions)
+    }
+
+    /// Check if some sequence of at most `max_depth` moves ends the game in a
+    /// tie, either with a full board or with no player able to win anymore.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG").unwrap();
+    /// assert!(!game.tie_reachable(2));
+    /// ```
+    #[must_use]
+    pub fn tie_reachable(&self, max_depth: u8) -> bool {
+        tie_reachable_from(&self.board, max_depth)
+    }
+
+    /// Classify the last move comparing threats, that is positions that would
+    /// complete a winning combination, before and after it. A move is
+    /// classified by the first class it matches, in the order of [`MoveClass`].
+    /// Return `None` if no move was played.
+    ///
+    /// ```
+    /// # use tris3d::game::{Game, MoveClass};
+    /// let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "ABCH").unwrap();
+    /// assert_eq!(game.classify_last_move(), Some(MoveClass::CreatedThreat));
+    /// ```
+    #[must_use]
+    pub fn classify_last_move(&self) -> Option<MoveClass> {
+        let (&last_move, previous_moves) = self.board.get_moves().split_last()?;
+        if self.board.status == BoardStatus::HasWinner {
+            return Some(MoveClass::Won);
+        }
+        let num_players = self.board.num_players();
+        let mut previous_board = Board::with_num_players(num_players).unwrap();
+        for &position in previous_moves {
+            previous_board.add_move(position).ok()?;
+        }
+        let player_index = (previous_moves.len() % num_players) as u8;
+        if self.board.threats(player_index).len() > previous_board.threats(player_index).len() {
+            return Some(MoveClass::CreatedThreat);
+        }
+        if (1..num_players as u8).any(|i| {
+            previous_board
+                .threats((player_index + i) % num_players as u8)
+                .contains(&last_move)
+        }) {
+            return Some(MoveClass::Blocked);
+        }
+        Some(MoveClass::Quiet)
+    }
+
+    /// Annotate every move played in the current round, comparing it with
+    /// the move recommended by [`Board::best_defense`] looking at most
+    /// `max_depth` moves ahead.
+    ///
+    /// ```
+    /// # let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFB").unwrap();
+    /// let annotations = game.annotate(1);
+    /// assert!(annotations[6].missed_win);
+    /// ```
+    #[must_use]
+    pub fn annotate(&self, max_depth: u8) -> Vec<MoveAnnotation> {
+        let num_players = self.board.num_players();
+        let mut board = Board::with_num_players(num_players).unwrap();
+        let mut annotations = Vec::with_capacity(self.board.get_num_moves());
+        for &position in self.board.get_moves() {
+            let player_index = (board.get_num_moves() % num_players) as u8;
+            let score_of = |position: char| {
+                let mut next_board = board.clone();
+                next_board.add_move(position).ok()?;
+                Some(next_board.worst_case_score(player_index, max_depth.saturating_sub(1)))
+            };
+            let was_best = board
+                .best_defense(player_index, max_depth)
+                .is_none_or(|best_position| score_of(position) >= score_of(best_position));
+            let threats = board.threats(player_index);
+            annotations.push(MoveAnnotation {
+                position,
+                was_best,
+                missed_win: !threats.is_empty() && !threats.contains(&position),
+            });
+            if board.add_move(position).is_err() {
+                break;
+            }
+        }
+        annotations
+    }
+
+    /// Play the given number of games to the end from the current position,
+    /// with random moves, and return the fraction of games won by every
+    /// player. The remainder is the fraction of ties.
+    /// The same seed gives the same result.
+    #[cfg(feature = "bot")]
+    #[must_use]
+    pub fn estimate_win_chances(&self, samples: usize, seed: u64) -> [f32; 3] {
+        let mut num_wins = [0_usize; 3];
+        let mut state = seed;
+        for _ in 0..samples {
+            let mut board = self.board.clone();
+            while board.status == BoardStatus::IsPlaying {
+                let positions = board.available_positions();
+                let index = next_random(&mut state) % positions.len() as u64;
+                board.add_move(positions[index as usize]).unwrap();
+            }
+            if let Some(winning_move_index) = board.winning_move_index() {
+                num_wins[winning_move_index % board.num_players()] += 1;
+            }
+        }
+        num_wins.map(|num| {
+            if samples == 0 {
+                0.0
+            } else {
+                num as f32 / samples as f32
+            }
+        })
+    }
+
+    /// Legal moves for the current player, paired with the evaluation of the
+    /// resulting board from the point of view of that player, best first.
+    /// Moves with the same score follow the order of the `POSITION` array.
+    #[must_use]
+    pub fn available_ranked(&self) -> Vec<(char, i32)> {
+        let Some(player_index) = self.current_player_index() else {
+            return Vec::new();
+        };
+        let player_id = &self.player_ids[usize::from(player_index)];
+        let mut board = self.board.clone();
+        let mut ranked: Vec<(char, i32)> = POSITION
+            .into_iter()
+            .filter(|&position| self.validate_move(player_id, position).is_ok())
+            .filter_map(|position| {
+                board
+                    .with_move(position, |board| board.evaluate_for(player_index))
+                    .ok()
+                    .map(|score| (position, score))
+            })
+            .collect();
+        ranked.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        ranked
+    }
+
+    /// Moves grouped by round, that is one move per player, for instance
+    /// `"1. A H G  2. * I F  3. V"`.
+    #[must_use]
+    pub fn pretty_notation(&self) -> String {
+        self.board
+            .get_moves()
+            .chunks(self.board.num_players())
+            .enumerate()
+            .map(|(index, round)| {
+                let round: Vec<String> = round.iter().map(char::to_string).collect();
+                format!("{}. {}", index + 1, round.join(" "))
+            })
+            .collect::<Vec<String>>()
+            .join("  ")
+    }
+
+    /// Export moves as CSV, one row per move with the following columns:
+    /// `move_number,player_id,position,x,y,z,won`.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("move_number,player_id,position,x,y,z,won\n");
+        let winning_move_index = self.board.winning_move_index();
+        for (index, &position) in self.board.get_moves().iter().enumerate() {
+            let player_id = &self.player_ids[index % self.board.num_players()];
+            let player_id = if player_id.contains([',', '"', '\n']) {
+                format!("\"{}\"", player_id.replace('"', "\"\""))
+            } else {
+                player_id.clone()
+            };
+            let (x, y, z) = vector_of_position(position).unwrap();
+            let won = winning_move_index == Some(index);
+            csv.push_str(&format!(
+                "{},{player_id},{position},{x},{y},{z},{won}\n",
+                index + 1
+            ));
+        }
+        csv
+    }
+
+    /// Move number, starting from 1, at which the given position was played.
+    /// Return `None` if the position is still empty.
+    #[must_use]
+    pub fn move_number_of(&self, position: char) -> Option<usize> {
+        self.board
+            .get_moves()
+            .iter()
+            .position(|&p| p == position)
+            .map(|index| index + 1)
+    }
+
+    /// Iterate over players in turn order, paired with their index.
+    pub fn players_iter(&self) -> impl Iterator<Item = (u8, &String)> {
+        (0..).zip(&self.player_ids)
+    }
+
+    #[must_use]
+    pub fn num_players(&self) -> usize {
+        self.player_ids.len()
+    }
+}
+
+// SplitMix64 pseudo random number generator.
+#[cfg(feature = "bot")]
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn tie_reachable_from(board: &Board, max_depth: u8) -> bool {
+    match board.status {
+        BoardStatus::HasWinner => return false,
+        BoardStatus::Tie => return true,
+        BoardStatus::IsPlaying => {}
+    }
+    if (0..board.num_players() as u8).all(|player_index| !board.can_still_win(player_index)) {
+        return true;
+    }
+    if max_depth == 0 {
+        return false;
+    }
+    POSITION.into_iter().any(|position| {
+        let mut board = board.clone();
+        board.add_move(position).is_ok() && tie_reachable_from(&board, max_depth - 1)
+    })
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Show the moves as a single string, for instance `"AHG*IFV"`.
+impl fmt::Debug for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Game")
+            .field("player_ids", &self.player_ids)
+            .field("status", &self.status)
+            .field("current_player_index", &self.current_player_index())
+            .field("moves", &self.board.get_moves().iter().collect::<String>())
+            .finish_non_exhaustive()
+    }
+}
+
+// Compare the state of the games, observers and callbacks excluded.
+impl PartialEq for Game {
+    fn eq(&self, other: &Self) -> bool {
+        self.board == other.board
+            && self.forbid_center_opening == other.forbid_center_opening
+            && self.player_ids == other.player_ids
+            && self.rotation == other.rotation
+            && self.series_score == other.series_score
+            && self.status == other.status
+    }
+}
+
+// Fields of a serialized game, checked against each other on
+// deserialization. Observers and callbacks are not serialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GameState {
+    board: Board,
+    forbid_center_opening: bool,
+    player_ids: Vec<String>,
+    rotation: usize,
+    series_score: [u32; 3],
+    status: Status,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Game {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = GameState::deserialize(deserializer)?;
+        let num_players = state.board.num_players();
+        let mut player_ids = state.player_ids.clone();
+        player_ids.sort();
+        player_ids.dedup();
+        let status = if state.player_ids.len() < num_players {
+            Status::WaitingForPlayers
+        } else if state.board.status == BoardStatus::IsPlaying {
+            Status::IsPlaying
+        } else {
+            Status::IsOver
+        };
+        if player_ids.len() != state.player_ids.len()
+            || state.player_ids.len() > num_players
+            || state.rotation >= num_players
+            || state.status != status
+            || (status == Status::WaitingForPlayers && state.board.get_num_moves() > 0)
+            || (state.forbid_center_opening && state.board.get_moves().first() == Some(&'*'))
+        {
+            let error = format!("{:?}", Error::InconsistentState);
+            return Err(serde::de::Error::custom(error));
+        }
+        Ok(Self {
+            board: state.board,
+            forbid_center_opening: state.forbid_center_opening,
+            observers: Vec::new(),
+            on_turn_start: None,
+            player_ids: state.player_ids,
+            rotation: state.rotation,
+            series_score: state.series_score,
+            status: state.status,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn new_game_is_waiting_for_players() {
+        assert_eq!(Game::new().num_players(), 0);
+        assert_eq!(Game::new().status, Status::WaitingForPlayers);
+    }
+
+    #[test]
+    fn add_player_increments_num_players() {
+        let mut game = Game::new();
+        match game.add_player(String::from("player 1")) {
+            Ok(_) => assert_eq!(game.num_players(), 1),
+            Err(_) => assert!(false),
+        }
+        match game.add_player(String::from("player 2")) {
+            Ok(_) => assert_eq!(game.num_players(), 2),
+            Err(_) => assert!(false),
+        }
+        match game.add_player(String::from("player 3")) {
+            Ok(_) => assert_eq!(game.num_players(), 3),
+            Err(_) => assert!(false),
+        }
+    }
+
+    #[test]
+    fn two_player_game() {
+        assert_eq!(
+            Game::with_player_count(4).unwrap_err(),
+            Error::InvalidNumberOfPlayers
+        );
+        let mut game = Game::with_player_count(2).unwrap();
+        game.add_player(String::from("Alice")).unwrap();
+        assert_eq!(game.status, Status::WaitingForPlayers);
+        game.add_player(String::from("Bob")).unwrap();
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(
+            game.add_player(String::from("Neuromancer")).unwrap_err(),
+            Error::CannotAddMoreThanTwoPlayers
+        );
+        assert_eq!(game.moves_until_win_possible(), 4);
+
+        for (player_id, position, num_winning_combinations) in [
+            ("Alice", 'A', 0),
+            ("Bob", 'H', 0),
+            ("Alice", '*', 0),
+            ("Bob", 'G', 0),
+        ] {
+            assert_eq!(
+                game.add_move(String::from(player_id), position),
+                Ok(num_winning_combinations)
+            );
+        }
+        assert_eq!(game.current_player().unwrap(), "Alice");
+        assert_eq!(
+            game.add_move(String::from("Bob"), 'V').unwrap_err(),
+            Error::PlayerMustWaitForTurn
+        );
+        assert_eq!(game.best_tactical_move(), Some('V'));
+        assert_eq!(game.add_move(String::from("Alice"), 'V'), Ok(1));
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.winner().unwrap(), "Alice");
+        assert_eq!(game.series_score(), [1, 0, 0]);
+        assert_eq!(game.pretty_notation(), "1. A H  2. * G  3. V");
+
+        let player_ids = vec![String::from("Alice"), String::from("Bob")];
+        let restored_game = Game::from_compact_state(player_ids, game.to_compact_state()).unwrap();
+        assert_eq!(restored_game.board().num_players(), 2);
+        assert_eq!(restored_game.board().ownership(), game.board().ownership());
+        assert_eq!(restored_game.winner().unwrap(), "Alice");
+
+        game.next_round();
+        assert_eq!(game.current_player().unwrap(), "Bob");
+        assert_eq!(game.board().num_players(), 2);
+    }
+
+    #[test]
+    fn add_player_checks_it_was_not_already_added() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+
+        assert_eq!(
+            game.add_player(String::from("Alice")).unwrap_err(),
+            Error::CannotAddSamePlayerTwice
+        );
+    }
+
+    #[test]
+    fn add_player_does_not_add_more_players_than_allowed() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(
+            game.add_player(String::from("Another player")).unwrap_err(),
+            Error::CannotAddMoreThanThreePlayers
+        );
+    }
+
+    #[test]
+    fn add_move_checks_that_game_is_not_waiting_for_players() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+
+        assert_eq!(
+            game.add_move(String::from("Alice"), 'A').unwrap_err(),
+            Error::GameNotStartedYet
+        );
+    }
+
+    #[test]
+    fn add_move_checks_that_player_is_playing() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(
+            game.add_move(String::from("Alice"), ' ').unwrap_err(),
+            Error::InvalidPosition
+        );
+    }
+
+    #[test]
+    fn add_move_checks_that_player_turn_is_correct() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(
+            game.add_move(String::from("Bob"), 'A').unwrap_err(),
+            Error::PlayerMustWaitForTurn
+        );
+    }
+
+    #[test]
+    fn add_move_checks_that_position_is_valid() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(
+            game.add_move(String::from("Another player"), 'A')
+                .unwrap_err(),
+            Error::PlayerNotFound
+        );
+    }
+
+    #[test]
+    fn add_move_checks_if_game_is_over() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        game.add_move(String::from("Alice"), 'A').unwrap();
+        game.add_move(String::from("Bob"), 'H').unwrap();
+        game.add_move(String::from("Neuromancer"), 'G').unwrap();
+        game.add_move(String::from("Alice"), '*').unwrap();
+        game.add_move(String::from("Bob"), 'I').unwrap();
+        game.add_move(String::from("Neuromancer"), 'F').unwrap();
+        game.add_move(String::from("Alice"), 'V').unwrap();
 
         assert_eq!(
             game.add_move(String::from("Bob"), 'B').unwrap_err(),
             Error::GameIsOver
         );
     }
+
+    #[test]
+    fn winner_is_the_last_player_moving() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        assert_eq!(Game::new().winner(), None);
+        let game = Game::quick_start(players, "AHG*IF").unwrap();
+        assert_eq!(game.winner(), None);
+        let game = Game::quick_start(players, "BAHG*IFV").unwrap();
+        assert_eq!(game.winner().unwrap(), "Bob");
+        let game = Game::quick_start(players, "CSPIEAKX*LHNOJBYUFQRDVGTZWM").unwrap();
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.winner(), None);
+    }
+
+    #[test]
+    fn moves_until_win_possible_counts_down() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        assert_eq!(Game::new().moves_until_win_possible(), 6);
+        let game = Game::quick_start(players, "AHG").unwrap();
+        assert_eq!(game.moves_until_win_possible(), 3);
+        let game = Game::quick_start(players, "AHG*IF").unwrap();
+        assert_eq!(game.moves_until_win_possible(), 0);
+        let game = Game::quick_start(players, "AHG*IFB").unwrap();
+        assert_eq!(game.moves_until_win_possible(), 0);
+    }
+
+    #[test]
+    fn status_line_describes_turn_and_outcome() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        assert_eq!(game.status_line(), "Waiting for players (1/3)");
+
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHGB").unwrap();
+        assert_eq!(game.status_line(), "Move 5 — Bob to play");
+
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*BIV").unwrap();
+        assert_eq!(game.status_line(), "Game over — Alice wins (1 line)");
+    }
+
+    #[test]
+    fn tie_reachable_searches_until_max_depth() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        let game = Game::quick_start(players, "NWVZPGMYIDUREKSAOHQXLFCJ").unwrap();
+        assert!(game.tie_reachable(3));
+
+        // Every sequence of the three last moves completes a winning combination.
+        let game = Game::quick_start(players, "JCEOGKBSIRLWQAMDXVN*PHUF").unwrap();
+        assert!(!game.tie_reachable(3));
+    }
+
+    #[test]
+    fn classify_last_move_works() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        assert_eq!(Game::new().classify_last_move(), None);
+        let game = Game::quick_start(players, "A").unwrap();
+        assert_eq!(game.classify_last_move(), Some(MoveClass::Quiet));
+        let game = Game::quick_start(players, "ABCH").unwrap();
+        assert_eq!(game.classify_last_move(), Some(MoveClass::CreatedThreat));
+        let game = Game::quick_start(players, "ABCHG").unwrap();
+        assert_eq!(game.classify_last_move(), Some(MoveClass::Blocked));
+        let game = Game::quick_start(players, "AHG*IFV").unwrap();
+        assert_eq!(game.classify_last_move(), Some(MoveClass::Won));
+    }
+
+    #[test]
+    fn annotate_flags_missed_wins() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFB").unwrap();
+        let annotations = game.annotate(1);
+        assert_eq!(
+            annotations.iter().map(|a| a.position).collect::<String>(),
+            "AHG*IFB"
+        );
+        assert!(annotations[..6].iter().all(|a| a.was_best && !a.missed_win));
+        assert!(!annotations[6].was_best);
+        assert!(annotations[6].missed_win);
+
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+        assert!(game.annotate(1).iter().all(|a| a.was_best && !a.missed_win));
+    }
+
+    #[test]
+    fn is_symmetric_to_compares_boards() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        let game = Game::quick_start(players, "AHG*IFV").unwrap();
+        // Reflection through the `x = 1` plane.
+        let mirrored_game = Game::quick_start(players, "GHA*IBT").unwrap();
+        assert!(game.is_symmetric_to(&mirrored_game));
+        // Same positions, taken by different players.
+        let other_game = Game::quick_start(players, "AHG*IVF").unwrap();
+        assert!(!game.is_symmetric_to(&other_game));
+    }
+
+    #[test]
+    fn moves_grouped_by_layer_keeps_move_order() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        for (player_id, position) in [
+            ("Alice", 'V'),
+            ("Bob", 'A'),
+            ("Neuromancer", '*'),
+            ("Alice", 'B'),
+            ("Bob", 'R'),
+            ("Neuromancer", 'K'),
+            ("Alice", 'E'),
+        ] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+
+        assert_eq!(
+            game.moves_grouped_by_layer(),
+            [vec!['A', 'B', 'E'], vec!['*', 'K'], vec!['V', 'R']]
+        );
+    }
+
+    #[test]
+    fn forbid_center_opening_rejects_center_as_first_move() {
+        let mut game = GameBuilder::new().forbid_center_opening(true).build();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(
+            game.add_move(String::from("Alice"), '*').unwrap_err(),
+            Error::OpeningRuleViolation
+        );
+    }
+
+    #[test]
+    fn center_opening_is_allowed_by_default() {
+        let mut game = GameBuilder::new().build();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        assert_eq!(game.add_move(String::from("Alice"), '*').unwrap(), 0);
+    }
+
+    #[test]
+    fn forbid_center_opening_allows_center_after_first_move() {
+        let mut game = GameBuilder::new().forbid_center_opening(true).build();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+
+        game.add_move(String::from("Alice"), 'A').unwrap();
+        assert_eq!(game.add_move(String::from("Bob"), '*').unwrap(), 0);
+    }
+
+    #[test]
+    fn current_player_index_follows_turns() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        assert_eq!(game.current_player_index(), None);
+        game.add_player(String::from("Neuromancer")).unwrap();
+        assert_eq!(game.current_player_index(), Some(0));
+
+        for (player_id, position, next_player_index) in [
+            ("Alice", 'A', Some(1)),
+            ("Bob", 'H', Some(2)),
+            ("Neuromancer", 'G', Some(0)),
+            ("Alice", '*', Some(1)),
+            ("Bob", 'I', Some(2)),
+            ("Neuromancer", 'F', Some(0)),
+            ("Alice", 'V', None),
+        ] {
+            game.add_move(String::from(player_id), position).unwrap();
+            assert_eq!(game.current_player_index(), next_player_index);
+        }
+    }
+
+    #[test]
+    fn current_player_can_always_move() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        assert_eq!(game.current_player(), None);
+        game.add_player(String::from("Neuromancer")).unwrap();
+        // Very first move.
+        assert_eq!(game.current_player().unwrap(), "Alice");
+
+        for position in ['A', 'H', 'G', '*', 'I', 'F', 'V'] {
+            let player_id = game.current_player().unwrap().clone();
+            game.add_move(player_id, position).unwrap();
+            if position == 'G' {
+                // Turns wrap around after the third player.
+                assert_eq!(game.current_player().unwrap(), "Alice");
+            }
+        }
+        assert_eq!(game.current_player(), None);
+    }
+
+    #[test]
+    fn debug_shows_players_and_moves() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+        game.add_move(String::from("Alice"), 'A').unwrap();
+        game.add_move(String::from("Bob"), '*').unwrap();
+
+        let debug = format!("{game:?}");
+        assert!(debug.contains("\"Alice\", \"Bob\", \"Neuromancer\""));
+        assert!(debug.contains("IsPlaying"));
+        assert!(debug.contains("current_player_index: Some(2)"));
+        assert!(debug.contains("moves: \"A*\""));
+    }
+
+    #[test]
+    fn move_number_of_works() {
+        let mut game = Game::new();
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+        game.add_move(String::from("Alice"), 'A').unwrap();
+        game.add_move(String::from("Bob"), 'H').unwrap();
+        game.add_move(String::from("Neuromancer"), 'G').unwrap();
+
+        assert_eq!(game.move_number_of('A'), Some(1));
+        assert_eq!(game.move_number_of('G'), Some(3));
+        assert_eq!(game.move_number_of('*'), None);
+    }
+
+    #[test]
+    fn quick_start_checks_moves() {
+        assert_eq!(
+            Game::quick_start(["Alice", "Bob", "Neuromancer"], "AA").unwrap_err(),
+            Error::PositionAlreadyTaken
+        );
+        assert_eq!(
+            Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFVB").unwrap_err(),
+            Error::GameIsOver
+        );
+        assert_eq!(
+            Game::quick_start(["Alice", "Bob", "Alice"], "").unwrap_err(),
+            Error::CannotAddSamePlayerTwice
+        );
+    }
+
+    #[test]
+    fn resume_replays_unfinished_games() {
+        let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+        let game = Game::resume(player_ids.to_vec(), "AHG*I").unwrap();
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.current_player_index(), Some(2));
+        let available_positions: Vec<char> = game
+            .available_ranked()
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(available_positions.len(), 22);
+        for position in "AHG*I".chars() {
+            assert!(!available_positions.contains(&position));
+        }
+
+        let game = Game::resume(player_ids.to_vec(), "AHG*IFV").unwrap();
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.current_player_index(), None);
+
+        assert_eq!(
+            Game::resume(player_ids[..1].to_vec(), "A").unwrap_err(),
+            Error::InvalidNumberOfPlayers
+        );
+        assert_eq!(
+            Game::resume(player_ids.to_vec(), "AHG*IFVB").unwrap_err(),
+            Error::GameIsOver
+        );
+    }
+
+    #[test]
+    fn resume_and_load_two_player_games() {
+        let player_ids = ["Alice", "Bob"].map(String::from);
+        let game = Game::resume(player_ids.to_vec(), "AHBI").unwrap();
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.current_player().unwrap(), "Alice");
+        let game = Game::load(player_ids.to_vec(), b"AHBIC").unwrap();
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.winner().unwrap(), "Alice");
+        let loaded_game = Game::load(player_ids.to_vec(), &game.to_compact_state()).unwrap();
+        assert_eq!(loaded_game.board.ownership(), game.board.ownership());
+        assert_eq!(loaded_game.status, Status::IsOver);
+    }
+
+    #[test]
+    fn from_moves_round_trip() {
+        let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+        let game = Game::from_moves(player_ids.clone(), &['A', 'H', 'G', '*']).unwrap();
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.current_player().unwrap(), "Bob");
+        let rebuilt_game = Game::from_moves(player_ids.clone(), game.moves()).unwrap();
+        assert_eq!(rebuilt_game.moves(), game.moves());
+
+        let game = Game::from_moves(player_ids.clone(), &['A', 'H', 'G', '*', 'I', 'F', 'V']);
+        assert_eq!(game.unwrap().status, Status::IsOver);
+        for (moves, error) in [
+            (vec!['A', 'H', 'A'], Error::PositionAlreadyTaken),
+            (vec!['A', 'Ç'], Error::InvalidPosition),
+            (
+                vec!['A', 'H', 'G', '*', 'I', 'F', 'V', 'B'],
+                Error::GameIsOver,
+            ),
+        ] {
+            assert_eq!(
+                Game::from_moves(player_ids.clone(), &moves).unwrap_err(),
+                error
+            );
+        }
+        let player_ids = ["Alice", "Bob", "Alice"].map(String::from);
+        assert_eq!(
+            Game::from_moves(player_ids, &[]).unwrap_err(),
+            Error::CannotAddSamePlayerTwice
+        );
+    }
+
+    #[test]
+    fn moves_round_trip() {
+        let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+        for notation in [
+            "AHG*I",
+            "AHG*IFV",
+            "JQI*BAEURHFNSKTCWPOYVZLD",
+            "CSPIEAKX*LHNOJBYUFQRDVGTZWM",
+        ] {
+            let game = Game::resume(player_ids.to_vec(), notation).unwrap();
+            let moves: String = game.moves().iter().collect();
+            assert_eq!(Game::resume(player_ids.to_vec(), &moves).unwrap(), game);
+            assert_eq!(
+                Game::from_moves(player_ids.clone(), game.moves()).unwrap(),
+                game
+            );
+        }
+        let game = Game::resume(player_ids.to_vec(), "AHG").unwrap();
+        assert_ne!(Game::resume(player_ids.to_vec(), "AHB").unwrap(), game);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+        game.next_round();
+        game.add_move(String::from("Bob"), 'A').unwrap();
+        let json = serde_json::to_string(&game).unwrap();
+        let restored_game: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored_game.moves(), game.moves());
+        assert_eq!(restored_game.status, game.status);
+        assert_eq!(restored_game.current_player(), game.current_player());
+        assert_eq!(restored_game.series_score(), game.series_score());
+
+        let json = json.replace(r#""rotation":1"#, r#""rotation":3"#);
+        assert!(serde_json::from_str::<Game>(&json).is_err());
+
+        // The center opening is forbidden, but the board opens on it.
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "*").unwrap();
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(serde_json::from_str::<Game>(&json).is_ok());
+        let json = json.replace(
+            r#""forbid_center_opening":false"#,
+            r#""forbid_center_opening":true"#,
+        );
+        assert!(serde_json::from_str::<Game>(&json).is_err());
+    }
+
+    #[test]
+    fn compact_state_round_trip() {
+        let player_ids = vec![
+            String::from("Alice"),
+            String::from("Bob"),
+            String::from("Neuromancer"),
+        ];
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*I").unwrap();
+        let state = game.to_compact_state();
+        // Game is playing and it is the third player turn.
+        assert_eq!(state[0], 0b1001);
+
+        let rebuilt_game = Game::from_compact_state(player_ids, state).unwrap();
+        assert_eq!(rebuilt_game.board.ownership(), game.board.ownership());
+        assert_eq!(rebuilt_game.status, Status::IsPlaying);
+        assert_eq!(rebuilt_game.current_player_index(), Some(2));
+    }
+
+    #[test]
+    fn load_detects_format() {
+        let player_ids = ["Alice", "Bob", "Neuromancer"].map(String::from);
+        // Eight moves of notation are not mistaken for a compact state.
+        for notation in ["AHG*I", "AHG*IFBC", "AHG*IFV"] {
+            let game = Game::resume(player_ids.to_vec(), notation).unwrap();
+            let loaded_game = Game::load(player_ids.to_vec(), notation.as_bytes()).unwrap();
+            assert_eq!(loaded_game.moves(), game.moves());
+            let loaded_game = Game::load(player_ids.to_vec(), &game.to_compact_state()).unwrap();
+            assert_eq!(loaded_game.to_compact_state(), game.to_compact_state());
+            assert_eq!(loaded_game.status, game.status);
+            #[cfg(feature = "serde")]
+            {
+                let json = serde_json::to_vec(&game).unwrap();
+                let loaded_game = Game::load(player_ids.to_vec(), &json).unwrap();
+                assert_eq!(loaded_game.moves(), game.moves());
+                assert_eq!(loaded_game.status, game.status);
+                assert_eq!(
+                    Game::load(player_ids[..2].to_vec(), &json).unwrap_err(),
+                    Error::InconsistentState
+                );
+            }
+        }
+        #[cfg(not(feature = "serde"))]
+        assert_eq!(
+            Game::load(player_ids.to_vec(), b"{}").unwrap_err(),
+            Error::InvalidPosition
+        );
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            Game::load(player_ids.to_vec(), b"{}").unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+
+    #[test]
+    fn from_compact_state_checks_state_is_consistent() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*I").unwrap();
+        let mut state = game.to_compact_state();
+        state[0] = 0b0101;
+        assert_eq!(
+            Game::from_compact_state(
+                vec![
+                    String::from("Alice"),
+                    String::from("Bob"),
+                    String::from("Neuromancer"),
+                ],
+                state
+            )
+            .unwrap_err(),
+            Error::InconsistentState
+        );
+        assert_eq!(
+            Game::from_compact_state(vec![String::from("Alice")], game.to_compact_state())
+                .unwrap_err(),
+            Error::InconsistentState
+        );
+    }
+
+    #[test]
+    fn on_turn_start_is_called_once_per_turn() {
+        let player_indexes = Arc::new(Mutex::new(Vec::new()));
+        let mut game = Game::new();
+        let turns = Arc::clone(&player_indexes);
+        game.on_turn_start(move |player_index| turns.lock().unwrap().push(player_index));
+
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        assert!(player_indexes.lock().unwrap().is_empty());
+        game.add_player(String::from("Neuromancer")).unwrap();
+        assert_eq!(*player_indexes.lock().unwrap(), vec![0]);
+
+        game.add_move(String::from("Alice"), 'A').unwrap();
+        game.add_move(String::from("Bob"), 'H').unwrap();
+        game.add_move(String::from("Neuromancer"), 'G').unwrap();
+        assert_eq!(
+            game.add_move(String::from("Bob"), '*').unwrap_err(),
+            Error::PlayerMustWaitForTurn
+        );
+        game.add_move(String::from("Alice"), '*').unwrap();
+        assert_eq!(*player_indexes.lock().unwrap(), vec![0, 1, 2, 0, 1]);
+
+        game.add_move(String::from("Bob"), 'I').unwrap();
+        game.add_move(String::from("Neuromancer"), 'F').unwrap();
+        game.add_move(String::from("Alice"), 'V').unwrap();
+        // No turn begins once the game is over.
+        assert_eq!(*player_indexes.lock().unwrap(), vec![0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    struct MoveCounter {
+        num_moves: Arc<Mutex<u32>>,
+        winner: Arc<Mutex<Option<u8>>>,
+    }
+
+    impl GameObserver for MoveCounter {
+        fn on_move(&mut self, _player_index: u8, _position: char) {
+            *self.num_moves.lock().unwrap() += 1;
+        }
+
+        fn on_win(&mut self, player_index: u8) {
+            *self.winner.lock().unwrap() = Some(player_index);
+        }
+    }
+
+    #[test]
+    fn observers_see_every_move() {
+        let counters = [(); 2].map(|_| (Arc::new(Mutex::new(0)), Arc::new(Mutex::new(None))));
+        let mut game = Game::new();
+        for (num_moves, winner) in &counters {
+            game.add_observer(MoveCounter {
+                num_moves: Arc::clone(num_moves),
+                winner: Arc::clone(winner),
+            });
+        }
+        game.add_player(String::from("Alice")).unwrap();
+        game.add_player(String::from("Bob")).unwrap();
+        game.add_player(String::from("Neuromancer")).unwrap();
+        for (player_id, position) in [("Alice", 'A'), ("Bob", 'H'), ("Neuromancer", 'G')] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+        // A rejected move is not notified.
+        game.add_move(String::from("Alice"), 'A').unwrap_err();
+        for (player_id, position) in [
+            ("Alice", '*'),
+            ("Bob", 'I'),
+            ("Neuromancer", 'F'),
+            ("Alice", 'V'),
+        ] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+        for (num_moves, winner) in &counters {
+            assert_eq!(*num_moves.lock().unwrap(), 7);
+            assert_eq!(*winner.lock().unwrap(), Some(0));
+        }
+    }
+
+    #[test]
+    fn add_move_leaves_game_unchanged_on_error() {
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AH").unwrap();
+        for (player_id, position, error) in [
+            ("Alice", 'G', Error::PlayerMustWaitForTurn),
+            ("Another player", 'G', Error::PlayerNotFound),
+            ("Neuromancer", ' ', Error::InvalidPosition),
+            ("Neuromancer", 'A', Error::PositionAlreadyTaken),
+        ] {
+            assert_eq!(
+                game.add_move(String::from(player_id), position)
+                    .unwrap_err(),
+                error
+            );
+            assert_eq!(game.board.get_num_moves(), 2);
+            assert_eq!(game.board.status, BoardStatus::IsPlaying);
+            assert_eq!(game.status, Status::IsPlaying);
+        }
+    }
+
+    #[test]
+    fn to_csv_has_a_row_per_move() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+        let csv = game.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "move_number,player_id,position,x,y,z,won");
+        assert_eq!(rows.len(), 1 + 7);
+        assert_eq!(rows[1], "1,Alice,A,0,0,0,false");
+        assert_eq!(rows[7], "7,Alice,V,2,2,2,true");
+    }
+
+    #[test]
+    fn to_csv_quotes_player_ids() {
+        let game = Game::quick_start(["Alice, Jr.", "Bob \"B\"", "Neuromancer"], "AH").unwrap();
+        let csv = game.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[1], "1,\"Alice, Jr.\",A,0,0,0,false");
+        assert_eq!(rows[2], "2,\"Bob \"\"B\"\"\",H,1,0,0,false");
+    }
+
+    #[test]
+    fn best_tactical_move_prefers_winning() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "ABCHE*").unwrap();
+        // Alice can win with 'G', Neuromancer with 'Y'.
+        assert_eq!(game.best_tactical_move(), Some('G'));
+    }
+
+    #[test]
+    fn best_tactical_move_blocks_opponent() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "ABCHE").unwrap();
+        // Neuromancer moves next and blocks Alice, who can win with 'G'.
+        assert_eq!(game.best_tactical_move(), Some('G'));
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "WPZVYMAO").unwrap();
+        // Neuromancer moves next and blocks Alice, who can win with '*',
+        // before Bob, who can win with 'E', 'G' or 'N'.
+        assert_eq!(game.best_tactical_move(), Some('*'));
+    }
+
+    #[test]
+    fn best_tactical_move_is_none_in_quiet_position() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AV").unwrap();
+        assert_eq!(game.best_tactical_move(), None);
+    }
+
+    #[test]
+    fn players_iter_pairs_ids_with_indexes() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "").unwrap();
+        assert_eq!(
+            game.players_iter().collect::<Vec<_>>(),
+            vec![
+                (0, &String::from("Alice")),
+                (1, &String::from("Bob")),
+                (2, &String::from("Neuromancer")),
+            ]
+        );
+    }
+
+    #[test]
+    fn series_score_counts_rounds_won() {
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IF").unwrap();
+        assert_eq!(game.series_score(), [0, 0, 0]);
+        game.add_move(String::from("Alice"), 'V').unwrap();
+        assert_eq!(game.series_score(), [1, 0, 0]);
+
+        game.next_round();
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.board.get_num_moves(), 0);
+        assert_eq!(
+            game.add_move(String::from("Alice"), 'A').unwrap_err(),
+            Error::PlayerMustWaitForTurn
+        );
+        for (player_id, position) in [
+            ("Bob", 'A'),
+            ("Neuromancer", 'H'),
+            ("Alice", 'G'),
+            ("Bob", '*'),
+            ("Neuromancer", 'I'),
+            ("Alice", 'F'),
+            ("Bob", 'V'),
+        ] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.series_score(), [1, 1, 0]);
+
+        game.next_round();
+        assert_eq!(game.players_iter().next().unwrap().1, "Neuromancer");
+        assert_eq!(game.series_score(), [1, 1, 0]);
+    }
+
+    #[test]
+    fn undo_move_gives_turn_back() {
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "").unwrap();
+        assert_eq!(
+            game.undo_move(String::from("Alice")).unwrap_err(),
+            Error::NoMoveToUndo
+        );
+        for (player_id, position) in [("Alice", 'A'), ("Bob", 'H'), ("Neuromancer", 'G')] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+        assert_eq!(
+            game.undo_move(String::from("Bob")).unwrap_err(),
+            Error::PlayerMustWaitForTurn
+        );
+        assert_eq!(
+            game.undo_move(String::from("Zed")).unwrap_err(),
+            Error::PlayerNotFound
+        );
+        assert_eq!(game.undo_move(String::from("Neuromancer")), Ok('G'));
+        assert_eq!(game.current_player().unwrap(), "Neuromancer");
+        game.add_move(String::from("Neuromancer"), 'G').unwrap();
+
+        for (player_id, position) in [
+            ("Alice", '*'),
+            ("Bob", 'I'),
+            ("Neuromancer", 'F'),
+            ("Alice", 'V'),
+        ] {
+            game.add_move(String::from(player_id), position).unwrap();
+        }
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.series_score(), [1, 0, 0]);
+        assert_eq!(game.undo_move(String::from("Alice")), Ok('V'));
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.series_score(), [0, 0, 0]);
+        assert_eq!(game.current_player().unwrap(), "Alice");
+        game.add_move(String::from("Alice"), 'E').unwrap();
+        assert_eq!(game.current_player().unwrap(), "Bob");
+    }
+
+    #[test]
+    fn step_with_bots_plays_practice_game() {
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "").unwrap();
+        assert_eq!(game.step_with_bots("Alice"), Ok(Vec::new()));
+        assert_eq!(game.step_with_bots("Trinity"), Err(Error::PlayerNotFound));
+        for (position, bot_moves) in [
+            ('A', vec!['*', 'H']),
+            ('G', vec!['B', 'W']),
+            ('C', vec!['I', 'F']),
+            ('E', vec!['Z']),
+        ] {
+            game.add_move(String::from("Alice"), position).unwrap();
+            assert_eq!(game.step_with_bots("Alice").unwrap(), bot_moves);
+        }
+        assert_eq!(game.status, Status::IsOver);
+        assert_eq!(game.winner().unwrap(), "Bob");
+
+        // Bots do not open on the center when the rule forbids it.
+        let mut game = GameBuilder::new().forbid_center_opening(true).build();
+        for player_id in ["Alice", "Bob", "Neuromancer"] {
+            game.add_player(String::from(player_id)).unwrap();
+        }
+        assert_eq!(game.step_with_bots("Neuromancer").unwrap().len(), 2);
+        assert_ne!(game.moves()[0], '*');
+    }
+
+    #[test]
+    fn undo_to_last_threat_rewinds_to_threat() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        // Alice threatens 'G' with the fourth move, then Bob blocks it.
+        let mut game = Game::quick_start(players, "ABCHGN").unwrap();
+        assert_eq!(game.undo_to_last_threat(), Ok(2));
+        assert_eq!(game.board.get_moves(), ['A', 'B', 'C', 'H']);
+        assert_eq!(game.current_player().unwrap(), "Bob");
+        assert_eq!(game.undo_to_last_threat(), Err(Error::NoMoveToUndo));
+        assert_eq!(game.board.get_num_moves(), 4);
+
+        let mut game = Game::quick_start(players, "AHG*IFV").unwrap();
+        assert_eq!(game.undo_to_last_threat(), Ok(1));
+        assert_eq!(game.status, Status::IsPlaying);
+        assert_eq!(game.series_score(), [0, 0, 0]);
+    }
+
+    #[cfg(feature = "bot")]
+    #[test]
+    fn estimate_win_chances_favors_strong_position() {
+        let players = ["Alice", "Bob", "Neuromancer"];
+        let game = Game::quick_start(players, "AHG*IFBCD").unwrap();
+        let chances = game.estimate_win_chances(200, 42);
+        assert_eq!(chances, game.estimate_win_chances(200, 42));
+        assert!(chances.iter().all(|&chance| (0.0..=1.0).contains(&chance)));
+        assert!(chances.iter().sum::<f32>() <= 1.0);
+        assert!(chances[0] > chances[1] && chances[0] > chances[2]);
+
+        let game = Game::quick_start(players, "AHG*IFV").unwrap();
+        assert_eq!(game.estimate_win_chances(10, 0), [1.0, 0.0, 0.0]);
+        assert_eq!(Game::new().estimate_win_chances(0, 0), [0.0; 3]);
+    }
+
+    #[test]
+    fn explain_move_agrees_with_add_move() {
+        let mut waiting_game = Game::new();
+        waiting_game.add_player(String::from("Alice")).unwrap();
+        let over_game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+        let mut center_game = GameBuilder::new().forbid_center_opening(true).build();
+        for player_id in ["Alice", "Bob", "Neuromancer"] {
+            center_game.add_player(String::from(player_id)).unwrap();
+        }
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AH").unwrap();
+
+        for (mut game, player_id, position, error) in [
+            (waiting_game, "Alice", 'A', Error::GameNotStartedYet),
+            (over_game, "Bob", 'B', Error::GameIsOver),
+            (center_game, "Alice", '*', Error::OpeningRuleViolation),
+            (game, "Another player", 'G', Error::PlayerNotFound),
+        ] {
+            assert_eq!(game.explain_move(player_id, position), Err(error.clone()));
+            assert_eq!(game.add_move(String::from(player_id), position), Err(error));
+        }
+
+        let mut game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AH").unwrap();
+        for (player_id, position, error) in [
+            ("Alice", 'G', Error::PlayerMustWaitForTurn),
+            ("Neuromancer", ' ', Error::InvalidPosition),
+            ("Neuromancer", 'A', Error::PositionAlreadyTaken),
+        ] {
+            assert_eq!(game.explain_move(player_id, position), Err(error.clone()));
+            assert_eq!(game.add_move(String::from(player_id), position), Err(error));
+        }
+        assert_eq!(game.explain_move("Neuromancer", 'G'), Ok(()));
+    }
+
+    #[test]
+    fn available_ranked_lists_legal_moves_best_first() {
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*").unwrap();
+        let ranked = game.available_ranked();
+        assert_eq!(ranked.len(), 27 - 4);
+        for &(position, _) in &ranked {
+            assert_eq!(game.explain_move("Bob", position), Ok(()));
+        }
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        let game = Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+        assert!(game.available_ranked().is_empty());
+
+        let mut game = GameBuilder::new().forbid_center_opening(true).build();
+        for player_id in ["Alice", "Bob", "Neuromancer"] {
+            game.add_player(String::from(player_id)).unwrap();
+        }
+        let ranked = game.available_ranked();
+        assert_eq!(ranked.len(), 26);
+        assert!(ranked.iter().all(|&(position, _)| position != '*'));
+    }
 }
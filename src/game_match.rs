@@ -0,0 +1,103 @@
+use crate::board::Board;
+use crate::errors::Error;
+use crate::game::Game;
+
+/// A participant of a [Match].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Player {
+    pub id: String,
+}
+
+/// A series of games between the same three players, keeping the score
+/// across rounds.
+///
+/// ```
+/// use tris3d::{Match, Player};
+///
+/// let mut tournament = Match::new();
+/// for id in ["Alice", "Bob", "Neuromancer"] {
+///     tournament.add_player(&Player { id: String::from(id) }).unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct Match {
+    game: Game,
+    players: Vec<Player>,
+}
+
+impl Match {
+    /// Create a match with no players.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a player, up to three like [`Game::add_player`].
+    pub fn add_player(&mut self, player: &Player) -> Result<(), Error> {
+        self.game.add_player(player.id.clone())?;
+        self.players.push(player.clone());
+        Ok(())
+    }
+
+    /// Play a move in the current round, see [`Game::add_move`].
+    pub fn add_move(&mut self, player: &Player, position: char) -> Result<u8, Error> {
+        self.game.add_move(player.id.clone(), position)
+    }
+
+    /// Start a new round, see [`Game::next_round`].
+    pub fn next_round(&mut self) {
+        self.game.next_round();
+    }
+
+    /// Game of the current round.
+    #[must_use]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Board of the current round.
+    #[must_use]
+    pub fn board(&self) -> &Board {
+        self.game.board()
+    }
+
+    /// Players in the order they were added.
+    #[must_use]
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// Number of rounds won by the given player.
+    /// Return `None` if the player is not part of the match.
+    #[must_use]
+    pub fn score(&self, player: &Player) -> Option<u32> {
+        let index = self.players.iter().position(|p| p == player)?;
+        Some(self.game.series_score()[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_player_checks_limit() {
+        let mut tournament = Match::new();
+        for id in ["Alice", "Bob", "Neuromancer"] {
+            tournament
+                .add_player(&Player {
+                    id: String::from(id),
+                })
+                .unwrap();
+        }
+        let player = Player {
+            id: String::from("Zed"),
+        };
+        assert_eq!(
+            tournament.add_player(&player).unwrap_err(),
+            Error::CannotAddMoreThanThreePlayers
+        );
+        assert_eq!(tournament.players().len(), 3);
+        assert_eq!(tournament.score(&player), None);
+    }
+}
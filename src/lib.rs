@@ -1,11 +1,18 @@
-mod board;
+pub mod board;
 mod errors;
 pub mod game;
+mod game_match;
+pub mod position;
+pub mod prelude;
 mod winning_combinations;
 mod z3;
 mod z3xz3xz3;
 
 use crate::game::Game;
+pub use crate::game_match::{Match, Player};
+pub use crate::winning_combinations::{
+    canonical_winning_lines, line_by_id, line_id, lines_through_counts,
+};
 
 /// Create an new [Game].
 ///
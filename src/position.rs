@@ -0,0 +1,169 @@
+//! Conversion between positions and their coordinates.
+//!
+//! ```
+//! use tris3d::position::{position_of_vector, vector_of_position};
+//!
+//! assert_eq!(vector_of_position('Q'), Some((1, 0, 1)));
+//! assert_eq!(position_of_vector((1, 0, 1)), Some('Q'));
+//! ```
+
+pub use crate::z3xz3xz3::Z3xZ3xZ3Vector;
+
+/// Coordinates of the given position, see the `POSITION` array.
+/// Return `None` if it is not a position.
+#[must_use]
+pub fn vector_of_position(position: char) -> Option<Z3xZ3xZ3Vector> {
+    match position {
+        'A' => Some((0, 0, 0)),
+        'H' => Some((1, 0, 0)),
+        'G' => Some((2, 0, 0)),
+        'B' => Some((0, 1, 0)),
+        'I' => Some((1, 1, 0)),
+        'F' => Some((2, 1, 0)),
+        'C' => Some((0, 2, 0)),
+        'D' => Some((1, 2, 0)),
+        'E' => Some((2, 2, 0)),
+        'J' => Some((0, 0, 1)),
+        'Q' => Some((1, 0, 1)),
+        'P' => Some((2, 0, 1)),
+        'K' => Some((0, 1, 1)),
+        '*' => Some((1, 1, 1)),
+        'O' => Some((2, 1, 1)),
+        'L' => Some((0, 2, 1)),
+        'M' => Some((1, 2, 1)),
+        'N' => Some((2, 2, 1)),
+        'R' => Some((0, 0, 2)),
+        'X' => Some((1, 0, 2)),
+        'Y' => Some((2, 0, 2)),
+        'S' => Some((0, 1, 2)),
+        'Z' => Some((1, 1, 2)),
+        'W' => Some((2, 1, 2)),
+        'T' => Some((0, 2, 2)),
+        'U' => Some((1, 2, 2)),
+        'V' => Some((2, 2, 2)),
+        _ => None,
+    }
+}
+
+/// Position with the given coordinates, inverse of [`vector_of_position`].
+/// Return `None` if any coordinate is not 0, 1 or 2.
+#[must_use]
+pub fn position_of_vector(vector: Z3xZ3xZ3Vector) -> Option<char> {
+    match vector.0 {
+        0 => match vector.1 {
+            0 => match vector.2 {
+                0 => Some('A'),
+                1 => Some('J'),
+                2 => Some('R'),
+                _ => None,
+            },
+            1 => match vector.2 {
+                0 => Some('B'),
+                1 => Some('K'),
+                2 => Some('S'),
+                _ => None,
+            },
+            2 => match vector.2 {
+                0 => Some('C'),
+                1 => Some('L'),
+                2 => Some('T'),
+                _ => None,
+            },
+            _ => None,
+        },
+        1 => match vector.1 {
+            0 => match vector.2 {
+                0 => Some('H'),
+                1 => Some('Q'),
+                2 => Some('X'),
+                _ => None,
+            },
+            1 => match vector.2 {
+                0 => Some('I'),
+                1 => Some('*'),
+                2 => Some('Z'),
+                _ => None,
+            },
+            2 => match vector.2 {
+                0 => Some('D'),
+                1 => Some('M'),
+                2 => Some('U'),
+                _ => None,
+            },
+            _ => None,
+        },
+        2 => match vector.1 {
+            0 => match vector.2 {
+                0 => Some('G'),
+                1 => Some('P'),
+                2 => Some('Y'),
+                _ => None,
+            },
+            1 => match vector.2 {
+                0 => Some('F'),
+                1 => Some('O'),
+                2 => Some('W'),
+                _ => None,
+            },
+            2 => match vector.2 {
+                0 => Some('E'),
+                1 => Some('N'),
+                2 => Some('V'),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::POSITION;
+
+    #[test]
+    fn position_of_vector_works() {
+        for (vector, position) in [
+            ((0, 0, 0), 'A'),
+            ((0, 1, 0), 'B'),
+            ((0, 2, 0), 'C'),
+            ((1, 2, 0), 'D'),
+            ((2, 2, 0), 'E'),
+            ((2, 1, 0), 'F'),
+            ((2, 0, 0), 'G'),
+            ((1, 0, 0), 'H'),
+            ((1, 1, 0), 'I'),
+            ((0, 0, 1), 'J'),
+            ((0, 1, 1), 'K'),
+            ((0, 2, 1), 'L'),
+            ((1, 2, 1), 'M'),
+            ((2, 2, 1), 'N'),
+            ((2, 1, 1), 'O'),
+            ((2, 0, 1), 'P'),
+            ((1, 0, 1), 'Q'),
+            ((0, 0, 2), 'R'),
+            ((0, 1, 2), 'S'),
+            ((0, 2, 2), 'T'),
+            ((1, 2, 2), 'U'),
+            ((2, 2, 2), 'V'),
+            ((2, 1, 2), 'W'),
+            ((2, 0, 2), 'Y'),
+            ((1, 0, 2), 'X'),
+            ((1, 1, 2), 'Z'),
+            ((1, 1, 1), '*'),
+        ] {
+            assert_eq!(position_of_vector(vector).unwrap(), position);
+        }
+    }
+
+    #[test]
+    fn position_of_vector_is_inverse_of_vector_of_position() {
+        for position in POSITION {
+            assert_eq!(
+                position,
+                position_of_vector(vector_of_position(position).unwrap()).unwrap()
+            )
+        }
+    }
+}
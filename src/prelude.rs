@@ -0,0 +1,17 @@
+//! Re-export the commonly used items.
+//!
+//! ```
+//! use tris3d::prelude::*;
+//!
+//! let mut game: Game = new_game();
+//! game.add_player(String::from("Alice")).unwrap();
+//! game.add_player(String::from("Bob")).unwrap();
+//! game.add_player(String::from("Neuromancer")).unwrap();
+//! let result: Result<u8, Error> = game.add_move(String::from("Alice"), 'A');
+//! assert_eq!(result, Ok(0));
+//! ```
+
+pub use crate::board::Board;
+pub use crate::errors::Error;
+pub use crate::game::{Game, GameBuilder, GameObserver};
+pub use crate::new_game;
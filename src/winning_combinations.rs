@@ -1,38 +1,8 @@
+use crate::board::POSITION;
 use crate::errors::Error;
+use crate::position::vector_of_position;
 use crate::z3xz3xz3::{are_equal, semi_sum, Z3xZ3xZ3Vector};
-
-fn vector_of_position(position: char) -> Option<Z3xZ3xZ3Vector> {
-    match position {
-        'A' => Some((0, 0, 0)),
-        'H' => Some((1, 0, 0)),
-        'G' => Some((2, 0, 0)),
-        'B' => Some((0, 1, 0)),
-        'I' => Some((1, 1, 0)),
-        'F' => Some((2, 1, 0)),
-        'C' => Some((0, 2, 0)),
-        'D' => Some((1, 2, 0)),
-        'E' => Some((2, 2, 0)),
-        'J' => Some((0, 0, 1)),
-        'Q' => Some((1, 0, 1)),
-        'P' => Some((2, 0, 1)),
-        'K' => Some((0, 1, 1)),
-        '*' => Some((1, 1, 1)),
-        'O' => Some((2, 1, 1)),
-        'L' => Some((0, 2, 1)),
-        'M' => Some((1, 2, 1)),
-        'N' => Some((2, 2, 1)),
-        'R' => Some((0, 0, 2)),
-        'X' => Some((1, 0, 2)),
-        'Y' => Some((2, 0, 2)),
-        'S' => Some((0, 1, 2)),
-        'Z' => Some((1, 1, 2)),
-        'W' => Some((2, 1, 2)),
-        'T' => Some((0, 2, 2)),
-        'U' => Some((1, 2, 2)),
-        'V' => Some((2, 2, 2)),
-        _ => None,
-    }
-}
+use std::sync::OnceLock;
 
 pub fn get_is_winning_combination(
     position_a: char,
@@ -150,12 +120,103 @@ pub fn get_is_winning_combination(
     Ok(false)
 }
 
+/// List every winning combination exactly once, computed on the first call.
+///
+/// Positions in each combination, as well as combinations themselves,
+/// follow the order of the `POSITION` array.
+///
+/// ```
+/// assert_eq!(tris3d::canonical_winning_lines().len(), 49);
+/// ```
+#[must_use]
+pub fn canonical_winning_lines() -> &'static [(char, char, char)] {
+    static LINES: OnceLock<Vec<(char, char, char)>> = OnceLock::new();
+    LINES.get_or_init(|| {
+        let mut lines = Vec::new();
+        for (i, &position_a) in POSITION.iter().enumerate() {
+            for (j, &position_b) in POSITION.iter().enumerate().skip(i + 1) {
+                for &position_c in POSITION.iter().skip(j + 1) {
+                    if get_is_winning_combination(position_a, position_b, position_c).unwrap() {
+                        lines.push((position_a, position_b, position_c));
+                    }
+                }
+            }
+        }
+        lines
+    })
+}
+
+/// Stable identifier of a winning combination, that is its index in
+/// [`canonical_winning_lines`]. Positions can be given in any order.
+/// Return `None` if it is not a winning combination.
+///
+/// ```
+/// assert_eq!(tris3d::line_id(('G', 'H', 'A')), Some(0));
+/// assert_eq!(tris3d::line_id(('A', 'H', 'B')), None);
+/// ```
+#[must_use]
+pub fn line_id(line: (char, char, char)) -> Option<u8> {
+    let mut positions = [line.0, line.1, line.2];
+    positions.sort_unstable();
+    canonical_winning_lines()
+        .iter()
+        .copied()
+        .position(|(position_a, position_b, position_c)| {
+            let mut line_positions = [position_a, position_b, position_c];
+            line_positions.sort_unstable();
+            line_positions == positions
+        })
+        .map(|id| id as u8)
+}
+
+/// Winning combination with the given identifier, see [`line_id`].
+/// Return `None` if there is no such combination.
+#[must_use]
+pub fn line_by_id(id: u8) -> Option<(char, char, char)> {
+    canonical_winning_lines().get(usize::from(id)).copied()
+}
+
+// Winning combinations passing through the given position, computed once.
+pub(crate) fn lines_through(position: char) -> &'static [(char, char, char)] {
+    static LINES_THROUGH: OnceLock<[Vec<(char, char, char)>; 27]> = OnceLock::new();
+    let Some(index) = POSITION.iter().position(|&p| p == position) else {
+        return &[];
+    };
+    &LINES_THROUGH.get_or_init(|| {
+        POSITION.map(|position| {
+            canonical_winning_lines()
+                .iter()
+                .copied()
+                .filter(|&(position_a, position_b, position_c)| {
+                    position == position_a || position == position_b || position == position_c
+                })
+                .collect()
+        })
+    })[index]
+}
+
+/// Count how many winning combinations pass through every position.
+/// Counts are indexed like the `POSITION` array.
+#[must_use]
+pub fn lines_through_counts() -> [u8; 27] {
+    let mut counts = [0; 27];
+    for &(position_a, position_b, position_c) in canonical_winning_lines() {
+        for (index, &position) in POSITION.iter().enumerate() {
+            if position == position_a || position == position_b || position == position_c {
+                counts[index] += 1;
+            }
+        }
+    }
+    counts
+}
+
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants, clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
-    use crate::board::POSITION;
+    use crate::position::position_of_vector;
 
-    // There are 76 winning combinations in the board.
+    // The table below has 76 rows.
     //
     // Let's start with the combinations perpendicular to the x-axis.
     // Consider that the x coordinate is fixed at 0.
@@ -193,6 +254,10 @@ mod tests {
     //
     // So there are 76 = 24 * 3 + 4 combinations,
     // considering the x, y and z-axis plus 4 comubinations on the cube diagonals.
+    //
+    // However, every combination parallel to an axis lies on two planes, hence
+    // it is listed twice: the 27 of them are counted 54 times. There are
+    // 49 = 76 - 27 distinct winning combinations.
     static WINNING_COMBINATIONS: [(Z3xZ3xZ3Vector, Z3xZ3xZ3Vector, Z3xZ3xZ3Vector); 76] = [
         // Combinations perpendicular to the x-axis: first plane.
         ((0, 0, 0), (0, 1, 0), (0, 2, 0)),
@@ -282,120 +347,6 @@ mod tests {
         ((0, 2, 0), (1, 1, 1), (2, 0, 2)),
     ];
 
-    fn position_of_vector(vector: Z3xZ3xZ3Vector) -> Option<char> {
-        match vector.0 {
-            0 => match vector.1 {
-                0 => match vector.2 {
-                    0 => Some('A'),
-                    1 => Some('J'),
-                    2 => Some('R'),
-                    _ => None,
-                },
-                1 => match vector.2 {
-                    0 => Some('B'),
-                    1 => Some('K'),
-                    2 => Some('S'),
-                    _ => None,
-                },
-                2 => match vector.2 {
-                    0 => Some('C'),
-                    1 => Some('L'),
-                    2 => Some('T'),
-                    _ => None,
-                },
-                _ => None,
-            },
-            1 => match vector.1 {
-                0 => match vector.2 {
-                    0 => Some('H'),
-                    1 => Some('Q'),
-                    2 => Some('X'),
-                    _ => None,
-                },
-                1 => match vector.2 {
-                    0 => Some('I'),
-                    1 => Some('*'),
-                    2 => Some('Z'),
-                    _ => None,
-                },
-                2 => match vector.2 {
-                    0 => Some('D'),
-                    1 => Some('M'),
-                    2 => Some('U'),
-                    _ => None,
-                },
-                _ => None,
-            },
-            2 => match vector.1 {
-                0 => match vector.2 {
-                    0 => Some('G'),
-                    1 => Some('P'),
-                    2 => Some('Y'),
-                    _ => None,
-                },
-                1 => match vector.2 {
-                    0 => Some('F'),
-                    1 => Some('O'),
-                    2 => Some('W'),
-                    _ => None,
-                },
-                2 => match vector.2 {
-                    0 => Some('E'),
-                    1 => Some('N'),
-                    2 => Some('V'),
-                    _ => None,
-                },
-                _ => None,
-            },
-            _ => None,
-        }
-    }
-
-    #[test]
-    fn position_of_vector_works() {
-        for (vector, position) in [
-            ((0, 0, 0), 'A'),
-            ((0, 1, 0), 'B'),
-            ((0, 2, 0), 'C'),
-            ((1, 2, 0), 'D'),
-            ((2, 2, 0), 'E'),
-            ((2, 1, 0), 'F'),
-            ((2, 0, 0), 'G'),
-            ((1, 0, 0), 'H'),
-            ((1, 1, 0), 'I'),
-            ((0, 0, 1), 'J'),
-            ((0, 1, 1), 'K'),
-            ((0, 2, 1), 'L'),
-            ((1, 2, 1), 'M'),
-            ((2, 2, 1), 'N'),
-            ((2, 1, 1), 'O'),
-            ((2, 0, 1), 'P'),
-            ((1, 0, 1), 'Q'),
-            ((0, 0, 2), 'R'),
-            ((0, 1, 2), 'S'),
-            ((0, 2, 2), 'T'),
-            ((1, 2, 2), 'U'),
-            ((2, 2, 2), 'V'),
-            ((2, 1, 2), 'W'),
-            ((2, 0, 2), 'Y'),
-            ((1, 0, 2), 'X'),
-            ((1, 1, 2), 'Z'),
-            ((1, 1, 1), '*'),
-        ] {
-            assert_eq!(position_of_vector(vector).unwrap(), position);
-        }
-    }
-
-    #[test]
-    fn position_of_vector_is_inverse_of_vector_of_position() {
-        for position in POSITION {
-            assert_eq!(
-                position,
-                position_of_vector(vector_of_position(position).unwrap()).unwrap()
-            )
-        }
-    }
-
     #[test]
     fn get_is_winning_combination_checks_arguments_are_distinct() {
         for (position_a, position_b, position_c) in
@@ -474,4 +425,84 @@ mod tests {
             );
         }
     }
+
+    fn position_index(position: char) -> usize {
+        POSITION.iter().position(|&p| p == position).unwrap()
+    }
+
+    fn normalize(line: (char, char, char)) -> (char, char, char) {
+        let mut positions = [line.0, line.1, line.2];
+        positions.sort_by_key(|&p| position_index(p));
+        (positions[0], positions[1], positions[2])
+    }
+
+    #[test]
+    fn canonical_winning_lines_are_distinct_and_normalized() {
+        let lines = canonical_winning_lines();
+        assert_eq!(lines.len(), 49);
+        for (i, &line) in lines.iter().enumerate() {
+            assert_eq!(normalize(line), line);
+            assert!(!lines[(i + 1)..].contains(&line));
+        }
+    }
+
+    #[test]
+    fn canonical_winning_lines_match_winning_combinations_table() {
+        let lines = canonical_winning_lines();
+        let mut table_lines = Vec::new();
+        for (vector_a, vector_b, vector_c) in WINNING_COMBINATIONS {
+            let line = normalize((
+                position_of_vector(vector_a).unwrap(),
+                position_of_vector(vector_b).unwrap(),
+                position_of_vector(vector_c).unwrap(),
+            ));
+            assert!(lines.contains(&line));
+            if !table_lines.contains(&line) {
+                table_lines.push(line);
+            }
+        }
+        // Combinations parallel to an axis are listed twice in the table.
+        assert_eq!(table_lines.len(), lines.len());
+    }
+
+    #[test]
+    fn line_id_round_trip() {
+        for id in 0..49 {
+            let line = line_by_id(id).unwrap();
+            assert_eq!(line_id(line), Some(id));
+            assert_eq!(line_id((line.2, line.0, line.1)), Some(id));
+        }
+        assert_eq!(line_by_id(49), None);
+        assert_eq!(line_id(('A', 'H', 'I')), None);
+        assert_eq!(line_id(('A', 'A', 'A')), None);
+        assert_eq!(line_id(('A', 'H', 'Ç')), None);
+    }
+
+    #[test]
+    fn lines_through_works() {
+        assert_eq!(lines_through('*').len(), 13);
+        assert_eq!(lines_through('A').len(), 7);
+        assert!(lines_through('A').contains(&('A', 'H', 'G')));
+        assert!(lines_through('Ç').is_empty());
+    }
+
+    #[test]
+    fn lines_through_counts_works() {
+        let counts = lines_through_counts();
+        assert_eq!(counts[position_index('*')], 13);
+        assert_eq!(counts.iter().max(), Some(&13));
+        // Corner.
+        assert_eq!(counts[position_index('A')], 7);
+        // Edge.
+        assert_eq!(counts[position_index('H')], 4);
+        // Face center.
+        assert_eq!(counts[position_index('I')], 5);
+        assert_eq!(
+            counts
+                .iter()
+                .map(|&count| usize::from(count))
+                .sum::<usize>(),
+            canonical_winning_lines().len() * 3
+        );
+    }
 }
@@ -20,3 +20,17 @@ fn play() {
 
     assert_eq!(game.status, tris3d::game::Status::IsOver);
 }
+
+#[test]
+fn quick_start() {
+    let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+
+    assert_eq!(game.status, tris3d::game::Status::IsOver);
+}
+
+#[test]
+fn pretty_notation() {
+    let game = tris3d::game::Game::quick_start(["Alice", "Bob", "Neuromancer"], "AHG*IFV").unwrap();
+
+    assert_eq!(game.pretty_notation(), "1. A H G  2. * I F  3. V");
+}
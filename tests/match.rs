@@ -0,0 +1,52 @@
+#[test]
+fn play_two_rounds() {
+    let mut tournament = tris3d::Match::new();
+
+    let alice = tris3d::Player {
+        id: String::from("Alice"),
+    };
+    let bob = tris3d::Player {
+        id: String::from("Bob"),
+    };
+    let neuromancer = tris3d::Player {
+        id: String::from("Neuromancer"),
+    };
+
+    tournament.add_player(&alice).unwrap();
+    tournament.add_player(&bob).unwrap();
+    tournament.add_player(&neuromancer).unwrap();
+
+    for (player, position) in [
+        (&alice, 'A'),
+        (&bob, 'H'),
+        (&neuromancer, 'G'),
+        (&alice, '*'),
+        (&bob, 'I'),
+        (&neuromancer, 'F'),
+    ] {
+        tournament.add_move(player, position).unwrap();
+    }
+    assert!(!tournament.board().has_tris());
+    tournament.add_move(&alice, 'V').unwrap();
+    assert!(tournament.board().has_tris());
+    assert_eq!(tournament.score(&alice), Some(1));
+
+    // Second player in turn order moves first in the second round.
+    tournament.next_round();
+    assert!(!tournament.board().has_tris());
+    for (player, position) in [
+        (&bob, 'A'),
+        (&neuromancer, 'H'),
+        (&alice, 'G'),
+        (&bob, '*'),
+        (&neuromancer, 'I'),
+        (&alice, 'F'),
+        (&bob, 'V'),
+    ] {
+        tournament.add_move(player, position).unwrap();
+    }
+    assert!(tournament.board().has_tris());
+    assert_eq!(tournament.score(&alice), Some(1));
+    assert_eq!(tournament.score(&bob), Some(1));
+    assert_eq!(tournament.score(&neuromancer), Some(0));
+}